@@ -2,6 +2,9 @@
 
 use thiserror::Error;
 
+/// Boxed error used as the underlying cause of a [`RipelError`]
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
 #[derive(Error, Debug)]
 pub enum RipelError {
     #[error("Event processing error: {0}")]
@@ -13,11 +16,29 @@ pub enum RipelError {
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
 
-    #[error("Database error: {0}")]
-    DatabaseError(String),
+    #[error("Database error: {message}")]
+    DatabaseError {
+        message: String,
+        #[source]
+        source: Option<BoxError>,
+    },
+
+    #[error("Kafka error: {message}")]
+    KafkaError {
+        message: String,
+        #[source]
+        source: Option<BoxError>,
+    },
+
+    #[error("Schema registry error: {message}")]
+    SchemaRegistryError {
+        message: String,
+        #[source]
+        source: Option<BoxError>,
+    },
 
-    #[error("Kafka error: {0}")]
-    KafkaError(String),
+    #[error("Payload too large: {size} bytes exceeds the limit of {limit} bytes")]
+    PayloadTooLarge { size: usize, limit: usize },
 
     #[error("Configuration error: {0}")]
     ConfigError(String),
@@ -25,17 +46,147 @@ pub enum RipelError {
     #[error("Network error: {0}")]
     NetworkError(#[from] tonic::transport::Error),
 
+    /// Boxed since `tonic::Status` would otherwise make every `Result` large
     #[error("gRPC error: {0}")]
-    GrpcError(#[from] tonic::Status),
+    GrpcError(Box<tonic::Status>),
 
     #[error("Internal error: {0}")]
     InternalError(String),
 }
 
+impl From<tonic::Status> for RipelError {
+    fn from(status: tonic::Status) -> Self {
+        RipelError::GrpcError(Box::new(status))
+    }
+}
+
+impl RipelError {
+    /// Database error wrapping the driver error that caused it
+    pub fn database(message: impl Into<String>, source: impl Into<BoxError>) -> Self {
+        RipelError::DatabaseError {
+            message: message.into(),
+            source: Some(source.into()),
+        }
+    }
+
+    /// Kafka error wrapping the client error that caused it
+    pub fn kafka(message: impl Into<String>, source: impl Into<BoxError>) -> Self {
+        RipelError::KafkaError {
+            message: message.into(),
+            source: Some(source.into()),
+        }
+    }
+
+    /// Schema registry error wrapping the HTTP client error that caused it
+    pub fn schema_registry(message: impl Into<String>, source: impl Into<BoxError>) -> Self {
+        RipelError::SchemaRegistryError {
+            message: message.into(),
+            source: Some(source.into()),
+        }
+    }
+
+    /// The error followed by each of its causes, `": "`-separated, for
+    /// places that keep a single message such as DLQ events
+    pub fn display_chain(&self) -> String {
+        let mut message = self.to_string();
+        let mut source = std::error::Error::source(self);
+        while let Some(cause) = source {
+            message.push_str(": ");
+            message.push_str(&cause.to_string());
+            source = cause.source();
+        }
+        message
+    }
+
+    /// Stable code for the error kind, used as DLQ error code and metric label
+    pub fn code(&self) -> &'static str {
+        match self {
+            RipelError::ProcessingError(_) => "PROCESSING_ERROR",
+            RipelError::StreamError(_) => "STREAM_ERROR",
+            RipelError::SerializationError(_) => "SERIALIZATION_ERROR",
+            RipelError::DatabaseError { .. } => "DATABASE_ERROR",
+            RipelError::KafkaError { .. } => "KAFKA_ERROR",
+            RipelError::SchemaRegistryError { .. } => "SCHEMA_REGISTRY_ERROR",
+            RipelError::PayloadTooLarge { .. } => "PAYLOAD_TOO_LARGE",
+            RipelError::ConfigError(_) => "CONFIG_ERROR",
+            RipelError::NetworkError(_) => "NETWORK_ERROR",
+            RipelError::GrpcError(_) => "GRPC_ERROR",
+            RipelError::InternalError(_) => "INTERNAL_ERROR",
+        }
+    }
+
+    /// Whether retrying the failed operation may succeed: I/O against
+    /// databases, brokers and remote services is transient, while bad input
+    /// and configuration fail the same way every time
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            RipelError::StreamError(_)
+                | RipelError::DatabaseError { .. }
+                | RipelError::KafkaError { .. }
+                | RipelError::SchemaRegistryError { .. }
+                | RipelError::NetworkError(_)
+                | RipelError::GrpcError(_)
+        )
+    }
+}
+
 pub type Result<T> = std::result::Result<T, RipelError>;
 
 impl From<anyhow::Error> for RipelError {
     fn from(err: anyhow::Error) -> Self {
         RipelError::InternalError(err.to_string())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+
+    #[test]
+    fn test_source_chain() {
+        let io_error = std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "refused");
+        let err = RipelError::database("Connection failed", io_error);
+
+        assert_eq!(err.to_string(), "Database error: Connection failed");
+        assert_eq!(err.display_chain(), "Database error: Connection failed: refused");
+
+        let source = err.source().expect("source should be preserved");
+        let io_error = source
+            .downcast_ref::<std::io::Error>()
+            .expect("source should be the wrapped io error");
+        assert_eq!(io_error.kind(), std::io::ErrorKind::ConnectionRefused);
+    }
+
+    #[test]
+    fn test_transient_classification() {
+        let kafka = RipelError::KafkaError {
+            message: "Broker unavailable".to_string(),
+            source: None,
+        };
+        assert!(kafka.is_transient());
+        assert_eq!(kafka.code(), "KAFKA_ERROR");
+
+        let config = RipelError::ConfigError("missing brokers".to_string());
+        assert!(!config.is_transient());
+        assert_eq!(config.code(), "CONFIG_ERROR");
+    }
+
+    #[test]
+    fn test_source_absent() {
+        let err = RipelError::KafkaError {
+            message: "Producer fenced".to_string(),
+            source: None,
+        };
+        assert!(err.source().is_none());
+        assert_eq!(err.to_string(), "Kafka error: Producer fenced");
+    }
+
+    #[test]
+    fn test_grpc_status_conversion() {
+        let err = RipelError::from(tonic::Status::unavailable("server down"));
+        assert!(matches!(&err, RipelError::GrpcError(status) if status.message() == "server down"));
+        assert!(err.is_transient());
+    }
+}
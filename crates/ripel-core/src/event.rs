@@ -1,5 +1,6 @@
 //! Core event types and utilities
 
+use crate::{Result, RipelError};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -14,6 +15,10 @@ pub struct RipelEvent {
     /// Event type/schema identifier
     pub event_type: String,
     
+    /// Version of the payload schema for this event type
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+    
     /// Source system identifier
     pub source: String,
     
@@ -43,6 +48,7 @@ impl RipelEvent {
         Self {
             id: Uuid::new_v4().to_string(),
             event_type: event_type.into(),
+            schema_version: default_schema_version(),
             source: source.into(),
             timestamp: Utc::now(),
             data,
@@ -52,6 +58,11 @@ impl RipelEvent {
         }
     }
 
+    /// Start building an event with a typed payload
+    pub fn builder() -> RipelEventBuilder {
+        RipelEventBuilder::new()
+    }
+
     /// Add metadata to the event
     pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.metadata.insert(key.into(), value.into());
@@ -70,10 +81,152 @@ impl RipelEvent {
         self
     }
 
+    /// Set the payload schema version
+    pub fn with_schema_version(mut self, version: u32) -> Self {
+        self.schema_version = version;
+        self
+    }
+
+    /// Whether the payload is at least schema version `min_version`, i.e.
+    /// carries every field a consumer written against that version expects
+    pub fn is_compatible_with(&self, min_version: u32) -> bool {
+        self.schema_version >= min_version
+    }
+
     /// Get the partition key, using event ID as fallback
     pub fn effective_partition_key(&self) -> &str {
         self.partition_key.as_deref().unwrap_or(&self.id)
     }
+
+    /// Approximate size of the event contents in bytes.
+    ///
+    /// Sums the lengths of all strings, numbers and metadata without
+    /// serializing the event, so it is cheap enough to call per event.
+    pub fn approximate_size(&self) -> usize {
+        let metadata_size: usize = self
+            .metadata
+            .iter()
+            .map(|(key, value)| key.len() + value.len())
+            .sum();
+
+        self.id.len()
+            + self.event_type.len()
+            + self.source.len()
+            + std::mem::size_of::<DateTime<Utc>>()
+            + json_value_size(&self.data)
+            + metadata_size
+            + self.correlation_id.len()
+            + self.partition_key.as_ref().map_or(0, String::len)
+    }
+}
+
+/// Schema version of events that predate versioning
+fn default_schema_version() -> u32 {
+    1
+}
+
+/// Fluent builder for [`RipelEvent`] accepting any `Serialize` payload
+#[derive(Debug)]
+pub struct RipelEventBuilder {
+    event_type: Option<String>,
+    schema_version: u32,
+    source: Option<String>,
+    data: serde_json::Result<serde_json::Value>,
+    metadata: HashMap<String, String>,
+    correlation_id: Option<String>,
+    partition_key: Option<String>,
+}
+
+impl RipelEventBuilder {
+    pub fn new() -> Self {
+        Self {
+            event_type: None,
+            schema_version: default_schema_version(),
+            source: None,
+            data: Ok(serde_json::Value::Null),
+            metadata: HashMap::new(),
+            correlation_id: None,
+            partition_key: None,
+        }
+    }
+
+    pub fn event_type(mut self, event_type: impl Into<String>) -> Self {
+        self.event_type = Some(event_type.into());
+        self
+    }
+
+    pub fn schema_version(mut self, version: u32) -> Self {
+        self.schema_version = version;
+        self
+    }
+
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Set the payload; serialization errors are reported by [`Self::build`]
+    pub fn data(mut self, data: impl Serialize) -> Self {
+        self.data = serde_json::to_value(data);
+        self
+    }
+
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn partition_key(mut self, key: impl Into<String>) -> Self {
+        self.partition_key = Some(key.into());
+        self
+    }
+
+    pub fn correlation_id(mut self, id: impl Into<String>) -> Self {
+        self.correlation_id = Some(id.into());
+        self
+    }
+
+    /// Build the event. Fails when the event type or source is missing or
+    /// the payload could not be serialized.
+    pub fn build(self) -> Result<RipelEvent> {
+        let event_type = self
+            .event_type
+            .ok_or_else(|| RipelError::ProcessingError("Event type is required".to_string()))?;
+        let source = self
+            .source
+            .ok_or_else(|| RipelError::ProcessingError("Event source is required".to_string()))?;
+
+        let mut event =
+            RipelEvent::new(event_type, source, self.data?).with_schema_version(self.schema_version);
+        event.metadata = self.metadata;
+        event.partition_key = self.partition_key;
+        if let Some(correlation_id) = self.correlation_id {
+            event.correlation_id = correlation_id;
+        }
+
+        Ok(event)
+    }
+}
+
+impl Default for RipelEventBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Approximate size in bytes of a JSON value's contents
+fn json_value_size(value: &serde_json::Value) -> usize {
+    match value {
+        serde_json::Value::Null => 0,
+        serde_json::Value::Bool(_) => 1,
+        serde_json::Value::Number(_) => 8,
+        serde_json::Value::String(s) => s.len(),
+        serde_json::Value::Array(items) => items.iter().map(json_value_size).sum(),
+        serde_json::Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| key.len() + json_value_size(value))
+            .sum(),
+    }
 }
 
 /// Database change event with CDC-specific information
@@ -209,6 +362,12 @@ impl DLQEvent {
         }
     }
 
+    /// Record how many processing attempts were made before dead-lettering
+    pub fn with_retry_count(mut self, retry_count: u32) -> Self {
+        self.retry_count = retry_count;
+        self
+    }
+
     pub fn increment_retry(mut self) -> Self {
         self.retry_count += 1;
         self.failed_at = Utc::now();
@@ -230,6 +389,89 @@ mod tests {
         assert_eq!(event.data, data);
         assert!(!event.id.is_empty());
         assert!(!event.correlation_id.is_empty());
+        assert_eq!(event.schema_version, 1);
+    }
+
+    #[test]
+    fn test_schema_version() {
+        let event = RipelEvent::new("test.event", "test-system", serde_json::json!({}));
+        assert_eq!(event.schema_version, 1);
+        assert!(event.is_compatible_with(1));
+        assert!(!event.is_compatible_with(2));
+
+        let event = event.with_schema_version(3);
+        assert!(event.is_compatible_with(2));
+        assert!(!event.is_compatible_with(4));
+
+        // Events serialized before versioning default to version 1
+        let mut json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["schema_version"], 3);
+        json.as_object_mut().unwrap().remove("schema_version");
+        let decoded: RipelEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.schema_version, 1);
+    }
+
+    #[derive(Serialize)]
+    struct UserCreated {
+        id: u64,
+        email: String,
+    }
+
+    #[test]
+    fn test_builder_with_typed_payload() {
+        let event = RipelEvent::builder()
+            .event_type("user.created")
+            .source("user-service")
+            .data(UserCreated {
+                id: 7,
+                email: "ada@example.com".to_string(),
+            })
+            .metadata("tenant", "acme")
+            .partition_key("user-7")
+            .correlation_id("corr-1")
+            .schema_version(2)
+            .build()
+            .unwrap();
+
+        assert_eq!(event.event_type, "user.created");
+        assert_eq!(event.source, "user-service");
+        assert_eq!(event.data, serde_json::json!({"id": 7, "email": "ada@example.com"}));
+        assert_eq!(event.metadata["tenant"], "acme");
+        assert_eq!(event.partition_key.as_deref(), Some("user-7"));
+        assert_eq!(event.correlation_id, "corr-1");
+        assert_eq!(event.schema_version, 2);
+        assert!(!event.id.is_empty());
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        assert!(RipelEvent::builder().source("s").build().is_err());
+        assert!(RipelEvent::builder().event_type("t").build().is_err());
+
+        // Maps with non-string keys cannot be represented as JSON
+        let payload = HashMap::from([((1, 2), "v")]);
+        let result = RipelEvent::builder()
+            .event_type("t")
+            .source("s")
+            .data(payload)
+            .build();
+        assert!(matches!(result, Err(RipelError::SerializationError(_))));
+    }
+
+    #[test]
+    fn test_approximate_size() {
+        let event = RipelEvent::new("t", "s", serde_json::json!({"k": "vv", "n": 1}))
+            .with_metadata("mk", "mv");
+        let base = event.id.len()
+            + event.correlation_id.len()
+            + std::mem::size_of::<DateTime<Utc>>()
+            + 2;
+
+        // data: "k" + "vv" + "n" + 8, metadata: "mk" + "mv"
+        assert_eq!(event.approximate_size(), base + 12 + 4);
+
+        let keyed = event.clone().with_partition_key("pk");
+        assert_eq!(keyed.approximate_size(), event.approximate_size() + 2);
     }
 
     #[test]
@@ -266,4 +508,14 @@ mod tests {
         assert_eq!(dlq.error_message, "Processing failed");
         assert_eq!(dlq.retry_count, 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_dlq_event_retry_count() {
+        let original = RipelEvent::new("test", "source", serde_json::json!({}));
+        let dlq = DLQEvent::new(original, "Processing failed", "PROC_ERROR", "processor")
+            .with_retry_count(3);
+
+        assert_eq!(dlq.retry_count, 3);
+        assert_eq!(dlq.increment_retry().retry_count, 4);
+    }
+}
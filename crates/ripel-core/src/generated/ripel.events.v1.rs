@@ -30,6 +30,9 @@ pub struct Event {
     /// Partition key for consistent routing
     #[prost(string, tag = "8")]
     pub partition_key: ::prost::alloc::string::String,
+    /// Payload schema version for event_type (0 is read as 1)
+    #[prost(uint32, tag = "9")]
+    pub schema_version: u32,
 }
 /// Database change event specific structure
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -3,6 +3,7 @@
 pub mod error;
 pub mod event;
 pub mod processor;
+mod proto;
 pub mod stream;
 pub mod generated {
     #![allow(clippy::all)]
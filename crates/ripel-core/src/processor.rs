@@ -1,16 +1,24 @@
 //! Event processor traits and implementations
 
-use crate::{RipelEvent, Result};
+use crate::{DLQEvent, RipelError, RipelEvent, Result};
 use async_trait::async_trait;
+use futures::future;
+use ripel_shared::{EventMetrics, RetryExecutor, RetryPolicy};
 use std::sync::Arc;
-use tokio::sync::mpsc;
-use tracing::{error, info, instrument};
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, watch};
+use tracing::{error, info, instrument, warn};
 
 /// Trait for processing events in the event-driven architecture
 #[async_trait]
 pub trait EventProcessor: Send + Sync {
     /// Process a single event
     async fn process(&self, event: RipelEvent) -> Result<()>;
+
+    /// Name used in logs and metrics; defaults to the type name
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
     
     /// Process a batch of events for better performance
     async fn process_batch(&self, events: Vec<RipelEvent>) -> Result<Vec<Result<()>>> {
@@ -34,15 +42,28 @@ pub trait EventProcessor: Send + Sync {
     }
 }
 
+/// How a [`ProcessorChain`] reacts to a failing processor
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChainMode {
+    /// Stop at the first failure and return its error
+    #[default]
+    FailFast,
+    /// Run every processor regardless, then return the first error. Suits
+    /// chains of independent sinks.
+    ContinueOnError,
+}
+
 /// Chain multiple processors together
 pub struct ProcessorChain {
     processors: Vec<Arc<dyn EventProcessor>>,
+    mode: ChainMode,
 }
 
 impl ProcessorChain {
     pub fn new() -> Self {
         Self {
             processors: Vec::new(),
+            mode: ChainMode::default(),
         }
     }
 
@@ -51,6 +72,12 @@ impl ProcessorChain {
         self
     }
 
+    /// Set how the chain handles a failing processor
+    pub fn with_mode(mut self, mode: ChainMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
     pub fn len(&self) -> usize {
         self.processors.len()
     }
@@ -70,13 +97,21 @@ impl Default for ProcessorChain {
 impl EventProcessor for ProcessorChain {
     #[instrument(skip(self, event), fields(event_id = %event.id, event_type = %event.event_type))]
     async fn process(&self, event: RipelEvent) -> Result<()> {
-        for (i, processor) in self.processors.iter().enumerate() {
+        let mut first_error = None;
+
+        for processor in &self.processors {
             if let Err(e) = processor.process(event.clone()).await {
-                error!("Processor {} failed: {}", i, e);
-                return Err(e);
+                error!("Processor {} failed: {}", processor.name(), e);
+                EventMetrics::processor_failed(processor.name(), e.code());
+
+                if self.mode == ChainMode::FailFast {
+                    return Err(e);
+                }
+                first_error.get_or_insert(e);
             }
         }
-        Ok(())
+
+        first_error.map_or(Ok(()), Err)
     }
 
     async fn start(&self) -> Result<()> {
@@ -94,13 +129,45 @@ impl EventProcessor for ProcessorChain {
     }
 }
 
+/// Event counts reported by [`EventPipeline::start`] once it stops
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PipelineStats {
+    pub processed: u64,
+    pub failed: u64,
+    pub dropped: u64,
+}
+
+/// Handle for asking a running [`EventPipeline`] to drain and stop
+#[derive(Clone)]
+pub struct PipelineShutdown {
+    tx: Arc<watch::Sender<bool>>,
+}
+
+impl PipelineShutdown {
+    /// Stop accepting new events; events already queued are still processed
+    /// before the pipeline shuts its processor down
+    pub fn shutdown(&self) {
+        self.tx.send_replace(true);
+    }
+}
+
 /// Event processing pipeline with concurrent processing
+///
+/// Events submitted through [`EventPipeline::sender`] are handed out by a
+/// dispatcher task to per-worker bounded queues, so workers never contend on
+/// a shared receiver. When every worker queue is full the dispatcher stops
+/// reading, which in turn applies backpressure to senders.
+///
+/// The pipeline stops once every sender is dropped, or when asked through
+/// [`EventPipeline::shutdown_handle`].
 pub struct EventPipeline {
     processor: Arc<dyn EventProcessor>,
-    event_tx: mpsc::Sender<RipelEvent>,
+    event_tx: Option<mpsc::Sender<RipelEvent>>,
     event_rx: Option<mpsc::Receiver<RipelEvent>>,
+    shutdown_tx: Arc<watch::Sender<bool>>,
     buffer_size: usize,
     worker_count: usize,
+    worker_queue_size: usize,
 }
 
 impl EventPipeline {
@@ -113,81 +180,285 @@ impl EventPipeline {
         
         Self {
             processor,
-            event_tx,
+            event_tx: Some(event_tx),
             event_rx: Some(event_rx),
+            shutdown_tx: Arc::new(watch::channel(false).0),
             buffer_size,
             worker_count,
+            worker_queue_size: 1,
         }
     }
 
+    /// Set how many events may be queued per worker before the dispatcher
+    /// waits for a free slot (minimum 1)
+    pub fn with_worker_queue_size(mut self, worker_queue_size: usize) -> Self {
+        self.worker_queue_size = worker_queue_size.max(1);
+        self
+    }
+
     /// Get a sender for submitting events to the pipeline
     pub fn sender(&self) -> mpsc::Sender<RipelEvent> {
-        self.event_tx.clone()
+        self.event_tx
+            .clone()
+            .expect("Pipeline already started")
+    }
+
+    /// Get a handle that triggers a graceful shutdown of the pipeline
+    pub fn shutdown_handle(&self) -> PipelineShutdown {
+        PipelineShutdown {
+            tx: self.shutdown_tx.clone(),
+        }
     }
 
-    /// Start the processing pipeline
+    /// Start the processing pipeline and run it until it stops, returning
+    /// how many events were processed, failed or dropped
     #[instrument(skip(self))]
-    pub async fn start(mut self) -> Result<()> {
+    pub async fn start(mut self) -> Result<PipelineStats> {
         let event_rx = self.event_rx.take().expect("Pipeline already started");
-        
+        let shutdown_rx = self.shutdown_tx.subscribe();
+
+        // Only external senders keep the pipeline alive, so it stops once they are all dropped
+        self.event_tx.take();
+
         info!(
             worker_count = self.worker_count,
             buffer_size = self.buffer_size,
+            worker_queue_size = self.worker_queue_size,
             "Starting event processing pipeline"
         );
 
         // Start the processor
         self.processor.start().await?;
 
-        // Create worker tasks
+        // Create worker tasks, each owning its own queue
         let mut handles = Vec::new();
-        let event_rx = Arc::new(tokio::sync::Mutex::new(event_rx));
-        
+        let mut worker_txs = Vec::with_capacity(self.worker_count);
+
         for worker_id in 0..self.worker_count {
             let processor = self.processor.clone();
-            let event_rx = event_rx.clone();
-            
+            let (worker_tx, mut worker_rx) = mpsc::channel::<RipelEvent>(self.worker_queue_size);
+            worker_txs.push(worker_tx);
+
             let handle = tokio::spawn(async move {
-                loop {
-                    let event = {
-                        let mut rx = event_rx.lock().await;
-                        rx.recv().await
-                    };
-                    
-                    match event {
-                        Some(event) => {
-                            if let Err(e) = processor.process(event.clone()).await {
-                                error!(
-                                    worker_id = worker_id,
-                                    event_id = %event.id,
-                                    error = %e,
-                                    "Event processing failed"
-                                );
-                            }
-                        }
-                        None => {
-                            info!(worker_id = worker_id, "Event channel closed, worker stopping");
-                            break;
+                let mut stats = PipelineStats::default();
+                while let Some(event) = worker_rx.recv().await {
+                    match processor.process(event.clone()).await {
+                        Ok(()) => stats.processed += 1,
+                        Err(e) => {
+                            stats.failed += 1;
+                            error!(
+                                worker_id = worker_id,
+                                event_id = %event.id,
+                                error = %e,
+                                "Event processing failed"
+                            );
                         }
                     }
                 }
+                info!(worker_id = worker_id, "Event channel closed, worker stopping");
+                stats
             });
-            
+
             handles.push(handle);
         }
 
-        // Wait for all workers to complete
+        // Fan events out to the workers until the input channel closes
+        let mut stats = PipelineStats {
+            dropped: Self::dispatch(event_rx, worker_txs, shutdown_rx).await,
+            ..PipelineStats::default()
+        };
+
+        // Wait for all workers to drain their queues
         for handle in handles {
-            if let Err(e) = handle.await {
-                error!("Worker task failed: {}", e);
+            match handle.await {
+                Ok(worker_stats) => {
+                    stats.processed += worker_stats.processed;
+                    stats.failed += worker_stats.failed;
+                }
+                Err(e) => error!("Worker task failed: {}", e),
             }
         }
 
         // Shutdown the processor
         self.processor.shutdown().await?;
         
-        info!("Event processing pipeline stopped");
-        Ok(())
+        info!(
+            processed = stats.processed,
+            failed = stats.failed,
+            dropped = stats.dropped,
+            "Event processing pipeline stopped"
+        );
+        Ok(stats)
+    }
+
+    /// Hand each incoming event to a worker with spare queue capacity,
+    /// waiting for the first free slot when all workers are saturated.
+    /// Dropping the worker senders on return lets the workers drain and stop.
+    ///
+    /// Once shutdown is requested the input channel is closed to new events
+    /// and the events already buffered in it are still dispatched. Returns
+    /// the number of events dropped for lack of workers.
+    async fn dispatch(
+        mut event_rx: mpsc::Receiver<RipelEvent>,
+        mut worker_txs: Vec<mpsc::Sender<RipelEvent>>,
+        mut shutdown_rx: watch::Receiver<bool>,
+    ) -> u64 {
+        let mut next_worker = 0;
+        let mut draining = false;
+
+        loop {
+            let event = tokio::select! {
+                event = event_rx.recv() => match event {
+                    Some(event) => event,
+                    None => break,
+                },
+                Ok(_) = shutdown_rx.wait_for(|stop| *stop), if !draining => {
+                    info!("Pipeline shutdown requested, draining queued events");
+                    event_rx.close();
+                    draining = true;
+                    continue;
+                }
+            };
+            let mut pending = Some(event);
+
+            // Round-robin over workers that can take the event right away
+            for offset in 0..worker_txs.len() {
+                let Some(event) = pending.take() else { break };
+                let index = (next_worker + offset) % worker_txs.len();
+                match worker_txs[index].try_send(event) {
+                    Ok(()) => next_worker = index + 1,
+                    Err(TrySendError::Full(event)) | Err(TrySendError::Closed(event)) => {
+                        pending = Some(event);
+                    }
+                }
+            }
+
+            let Some(event) = pending else { continue };
+
+            // Every worker is busy, so wait for whichever frees a slot first
+            loop {
+                worker_txs.retain(|tx| !tx.is_closed());
+                if worker_txs.is_empty() {
+                    error!(event_id = %event.id, "No workers available, dropping events");
+                    event_rx.close();
+                    let mut dropped = 1;
+                    while event_rx.recv().await.is_some() {
+                        dropped += 1;
+                    }
+                    return dropped;
+                }
+
+                let reservations = worker_txs.iter().map(|tx| Box::pin(tx.reserve()));
+                if let (Ok(permit), index, _) = future::select_all(reservations).await {
+                    permit.send(event);
+                    next_worker = index + 1;
+                    break;
+                }
+            }
+        }
+
+        0
+    }
+}
+
+/// Destination for events that failed processing for good
+#[async_trait]
+pub trait DeadLetterSink: Send + Sync {
+    /// Store `dlq_event` for later inspection or replay
+    async fn send(&self, dlq_event: DLQEvent) -> Result<()>;
+}
+
+/// Processor that retries transient failures of an inner processor and
+/// dead-letters events that still fail.
+///
+/// Only errors for which [`RipelError::is_transient`] holds are retried.
+/// Once an event has been dead-lettered `process` succeeds; if the sink
+/// fails too, the original processing error is returned.
+pub struct ResilientProcessor<P: RetryPolicy> {
+    inner: Arc<dyn EventProcessor>,
+    retry: RetryExecutor<P>,
+    dlq: Arc<dyn DeadLetterSink>,
+    destination: String,
+}
+
+impl<P: RetryPolicy> ResilientProcessor<P> {
+    pub fn new(inner: Arc<dyn EventProcessor>, policy: P, dlq: Arc<dyn DeadLetterSink>) -> Self {
+        let retry = RetryExecutor::new(policy).retry_if(|error| {
+            error
+                .downcast_ref::<RipelError>()
+                .is_some_and(RipelError::is_transient)
+        });
+
+        Self {
+            inner,
+            retry,
+            dlq,
+            destination: "processor".to_string(),
+        }
+    }
+
+    /// Name recorded as the failed destination of dead-lettered events
+    pub fn with_destination(mut self, destination: impl Into<String>) -> Self {
+        self.destination = destination.into();
+        self
+    }
+}
+
+#[async_trait]
+impl<P: RetryPolicy> EventProcessor for ResilientProcessor<P> {
+    #[instrument(skip(self, event), fields(event_id = %event.id, event_type = %event.event_type))]
+    async fn process(&self, event: RipelEvent) -> Result<()> {
+        let mut attempts = 0;
+        let result = self
+            .retry
+            .execute(|| {
+                attempts += 1;
+                let inner = self.inner.clone();
+                let event = event.clone();
+                Box::pin(async move { inner.process(event).await })
+            })
+            .await;
+
+        let Err(error) = result else {
+            return Ok(());
+        };
+
+        EventMetrics::event_failed(&event.event_type, error.code());
+        let event_id = event.id.clone();
+        let dlq_event = DLQEvent::new(event, error.display_chain(), error.code(), &self.destination)
+            .with_retry_count(attempts);
+
+        match self.dlq.send(dlq_event).await {
+            Ok(()) => {
+                warn!(
+                    event_id = %event_id,
+                    attempts = attempts,
+                    error = %error,
+                    "Event dead-lettered after processing failed"
+                );
+                Ok(())
+            }
+            Err(dlq_error) => {
+                error!(
+                    event_id = %event_id,
+                    dlq_error = %dlq_error,
+                    "Failed to dead-letter event"
+                );
+                Err(error)
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn start(&self) -> Result<()> {
+        self.inner.start().await
+    }
+
+    async fn shutdown(&self) -> Result<()> {
+        self.inner.shutdown().await
     }
 }
 
@@ -255,6 +526,49 @@ mod tests {
         assert_eq!(processor2.get_processed_events().await.len(), 1);
     }
 
+    struct FailingProcessor;
+
+    #[async_trait]
+    impl EventProcessor for FailingProcessor {
+        async fn process(&self, _event: RipelEvent) -> Result<()> {
+            Err(RipelError::ProcessingError("sink rejected event".to_string()))
+        }
+
+        fn name(&self) -> &str {
+            "failing-sink"
+        }
+    }
+
+    #[test]
+    fn test_processor_names() {
+        let processors: Vec<Arc<dyn EventProcessor>> =
+            vec![Arc::new(FailingProcessor), Arc::new(TestProcessor::new())];
+
+        assert_eq!(processors[0].name(), "failing-sink");
+        assert!(processors[1].name().ends_with("::TestProcessor"));
+    }
+
+    #[tokio::test]
+    async fn test_processor_chain_modes() {
+        let processor = Arc::new(TestProcessor::new());
+        let event = RipelEvent::new("test", "source", json!({}));
+
+        let fail_fast = ProcessorChain::new()
+            .add_processor(Arc::new(FailingProcessor))
+            .add_processor(processor.clone());
+        assert!(fail_fast.process(event.clone()).await.is_err());
+        assert!(processor.get_processed_events().await.is_empty());
+
+        let continue_on_error = ProcessorChain::new()
+            .with_mode(ChainMode::ContinueOnError)
+            .add_processor(Arc::new(FailingProcessor))
+            .add_processor(processor.clone());
+        let result = continue_on_error.process(event.clone()).await;
+
+        assert!(matches!(result, Err(RipelError::ProcessingError(_))));
+        assert_eq!(processor.get_processed_events().await.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_event_pipeline() {
         let processor = Arc::new(TestProcessor::new());
@@ -271,13 +585,214 @@ mod tests {
             sender.send(event).await.unwrap();
         }
         
-        // Close sender and wait for pipeline to finish
+        // Closing the only sender stops the pipeline
         drop(sender);
-        sleep(Duration::from_millis(100)).await;
-        
-        pipeline_handle.abort(); // Force stop for test
-        
-        // Check that events were processed
-        assert!(!processor.get_processed_events().await.is_empty());
+        let stats = tokio::time::timeout(Duration::from_secs(1), pipeline_handle)
+            .await
+            .expect("pipeline did not stop")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(stats.processed, 5);
+        assert_eq!(processor.get_processed_events().await.len(), 5);
+    }
+
+    struct SlowProcessor {
+        processed: std::sync::atomic::AtomicUsize,
+        shut_down_after: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EventProcessor for SlowProcessor {
+        async fn process(&self, _event: RipelEvent) -> Result<()> {
+            sleep(Duration::from_millis(5)).await;
+            self.processed.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn shutdown(&self) -> Result<()> {
+            let processed = self.processed.load(std::sync::atomic::Ordering::SeqCst);
+            self.shut_down_after.store(processed, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_event_pipeline_graceful_shutdown_drains_queue() {
+        let processor = Arc::new(SlowProcessor {
+            processed: std::sync::atomic::AtomicUsize::new(0),
+            shut_down_after: std::sync::atomic::AtomicUsize::new(0),
+        });
+        let pipeline = EventPipeline::new(processor.clone(), 32, 2);
+        let sender = pipeline.sender();
+        let shutdown = pipeline.shutdown_handle();
+
+        for i in 0..20 {
+            sender.send(RipelEvent::new("test", "source", json!({"index": i}))).await.unwrap();
+        }
+        let pipeline_handle = tokio::spawn(pipeline.start());
+        shutdown.shutdown();
+
+        // The sender is still alive, so only the shutdown request stops the pipeline
+        let stats = tokio::time::timeout(Duration::from_secs(2), pipeline_handle)
+            .await
+            .expect("pipeline did not drain")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            stats,
+            PipelineStats {
+                processed: 20,
+                failed: 0,
+                dropped: 0,
+            }
+        );
+        assert_eq!(processor.shut_down_after.load(std::sync::atomic::Ordering::SeqCst), 20);
+        assert!(sender.send(RipelEvent::new("test", "source", json!({}))).await.is_err());
+    }
+
+    struct BarrierProcessor {
+        barrier: tokio::sync::Barrier,
+        processed: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EventProcessor for BarrierProcessor {
+        async fn process(&self, _event: RipelEvent) -> Result<()> {
+            self.barrier.wait().await;
+            self.processed.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        }
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_event_pipeline_workers_run_concurrently() {
+        let worker_count = 3;
+        let processor = Arc::new(BarrierProcessor {
+            barrier: tokio::sync::Barrier::new(worker_count),
+            processed: std::sync::atomic::AtomicUsize::new(0),
+        });
+        let pipeline = EventPipeline::new(processor.clone(), 10, worker_count);
+        let sender = pipeline.sender();
+
+        let pipeline_handle = tokio::spawn(pipeline.start());
+
+        for i in 0..worker_count {
+            let event = RipelEvent::new("test", "source", json!({"index": i}));
+            sender.send(event).await.unwrap();
+        }
+        drop(sender);
+
+        // The barrier only releases once every worker is inside `process`
+        tokio::time::timeout(Duration::from_secs(1), pipeline_handle)
+            .await
+            .expect("workers did not make concurrent progress")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            processor.processed.load(std::sync::atomic::Ordering::SeqCst),
+            worker_count
+        );
+    }
+
+    /// Fails with `error` until `failures` attempts have been made
+    struct FlakyProcessor {
+        failures: u32,
+        error: fn() -> RipelError,
+        attempts: std::sync::atomic::AtomicU32,
+    }
+
+    #[async_trait]
+    impl EventProcessor for FlakyProcessor {
+        async fn process(&self, _event: RipelEvent) -> Result<()> {
+            let attempt = self.attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            if attempt <= self.failures {
+                return Err((self.error)());
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingSink {
+        events: std::sync::Mutex<Vec<DLQEvent>>,
+    }
+
+    #[async_trait]
+    impl DeadLetterSink for RecordingSink {
+        async fn send(&self, dlq_event: DLQEvent) -> Result<()> {
+            self.events.lock().unwrap().push(dlq_event);
+            Ok(())
+        }
+    }
+
+    fn broker_down() -> RipelError {
+        RipelError::KafkaError {
+            message: "Broker unavailable".to_string(),
+            source: None,
+        }
+    }
+
+    fn resilient(
+        inner: Arc<FlakyProcessor>,
+        sink: Arc<RecordingSink>,
+    ) -> ResilientProcessor<ripel_shared::FixedInterval> {
+        let policy = ripel_shared::FixedInterval::new(Duration::from_millis(1), 3);
+        ResilientProcessor::new(inner, policy, sink).with_destination("orders-sink")
+    }
+
+    #[tokio::test]
+    async fn test_resilient_processor_retries_transient_errors() {
+        let inner = Arc::new(FlakyProcessor {
+            failures: 2,
+            error: broker_down,
+            attempts: std::sync::atomic::AtomicU32::new(0),
+        });
+        let sink = Arc::new(RecordingSink::default());
+        let processor = resilient(inner.clone(), sink.clone());
+
+        processor.process(RipelEvent::new("test", "source", json!({}))).await.unwrap();
+
+        assert_eq!(inner.attempts.load(std::sync::atomic::Ordering::SeqCst), 3);
+        assert!(sink.events.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resilient_processor_dead_letters_persistent_failures() {
+        let inner = Arc::new(FlakyProcessor {
+            failures: u32::MAX,
+            error: broker_down,
+            attempts: std::sync::atomic::AtomicU32::new(0),
+        });
+        let sink = Arc::new(RecordingSink::default());
+        let processor = resilient(inner.clone(), sink.clone());
+
+        let event = RipelEvent::new("test", "source", json!({}));
+        processor.process(event.clone()).await.unwrap();
+
+        let dead_lettered = sink.events.lock().unwrap();
+        assert_eq!(dead_lettered.len(), 1);
+        assert_eq!(dead_lettered[0].original_event.id, event.id);
+        assert_eq!(dead_lettered[0].error_code, "KAFKA_ERROR");
+        assert_eq!(dead_lettered[0].retry_count, 3);
+        assert_eq!(dead_lettered[0].failed_destination, "orders-sink");
+    }
+
+    #[tokio::test]
+    async fn test_resilient_processor_does_not_retry_permanent_errors() {
+        let inner = Arc::new(FlakyProcessor {
+            failures: u32::MAX,
+            error: || RipelError::ProcessingError("invalid payload".to_string()),
+            attempts: std::sync::atomic::AtomicU32::new(0),
+        });
+        let sink = Arc::new(RecordingSink::default());
+        let processor = resilient(inner.clone(), sink.clone());
+
+        processor.process(RipelEvent::new("test", "source", json!({}))).await.unwrap();
+
+        assert_eq!(inner.attempts.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(sink.events.lock().unwrap()[0].retry_count, 1);
+    }
+}
@@ -0,0 +1,304 @@
+//! Conversions between the core event types and their protobuf counterparts
+//!
+//! Protobuf has no notion of unset scalars, so empty strings and zero values
+//! in the wire types are read back as `None` (or schema version 1).
+
+use crate::{
+    DatabaseChangeEvent, OperationType, ProtoDatabaseChangeEvent, ProtoEvent, ProtoOperationType,
+    Result, RipelError, RipelEvent,
+};
+use chrono::{DateTime, Utc};
+use prost_types::{value::Kind, ListValue, Struct, Timestamp, Value};
+
+impl TryFrom<RipelEvent> for ProtoEvent {
+    type Error = RipelError;
+
+    /// Fails when the event data is neither a JSON object nor null, since a
+    /// protobuf `Struct` can only hold objects
+    fn try_from(event: RipelEvent) -> Result<Self> {
+        Ok(Self {
+            data: json_to_struct(event.data)?,
+            id: event.id,
+            event_type: event.event_type,
+            source: event.source,
+            timestamp: Some(to_timestamp(event.timestamp)),
+            metadata: event.metadata,
+            correlation_id: event.correlation_id,
+            partition_key: event.partition_key.unwrap_or_default(),
+            schema_version: event.schema_version,
+        })
+    }
+}
+
+impl TryFrom<ProtoEvent> for RipelEvent {
+    type Error = RipelError;
+
+    fn try_from(event: ProtoEvent) -> Result<Self> {
+        let timestamp = event
+            .timestamp
+            .ok_or_else(|| RipelError::ProcessingError("Event timestamp is missing".to_string()))?;
+
+        Ok(Self {
+            id: event.id,
+            event_type: event.event_type,
+            schema_version: event.schema_version.max(1),
+            source: event.source,
+            timestamp: from_timestamp(&timestamp)?,
+            data: event.data.map_or(serde_json::Value::Null, struct_to_json),
+            metadata: event.metadata,
+            correlation_id: event.correlation_id,
+            partition_key: Some(event.partition_key).filter(|key| !key.is_empty()),
+        })
+    }
+}
+
+impl From<OperationType> for ProtoOperationType {
+    fn from(operation: OperationType) -> Self {
+        match operation {
+            OperationType::Insert => ProtoOperationType::Insert,
+            OperationType::Update => ProtoOperationType::Update,
+            OperationType::Delete => ProtoOperationType::Delete,
+            OperationType::Ddl => ProtoOperationType::Ddl,
+        }
+    }
+}
+
+impl TryFrom<ProtoOperationType> for OperationType {
+    type Error = RipelError;
+
+    fn try_from(operation: ProtoOperationType) -> Result<Self> {
+        match operation {
+            ProtoOperationType::Insert => Ok(OperationType::Insert),
+            ProtoOperationType::Update => Ok(OperationType::Update),
+            ProtoOperationType::Delete => Ok(OperationType::Delete),
+            ProtoOperationType::Ddl => Ok(OperationType::Ddl),
+            ProtoOperationType::Unspecified => Err(RipelError::ProcessingError(
+                "Operation type is unspecified".to_string(),
+            )),
+        }
+    }
+}
+
+impl TryFrom<DatabaseChangeEvent> for ProtoDatabaseChangeEvent {
+    type Error = RipelError;
+
+    fn try_from(change: DatabaseChangeEvent) -> Result<Self> {
+        Ok(Self {
+            base_event: Some(ProtoEvent::try_from(change.base_event)?),
+            operation: ProtoOperationType::from(change.operation) as i32,
+            database: change.database,
+            table: change.table,
+            before: change.before.map(json_to_struct).transpose()?.flatten(),
+            after: change.after.map(json_to_struct).transpose()?.flatten(),
+            transaction_id: change.transaction_id.unwrap_or_default(),
+            lsn: change.lsn.unwrap_or_default(),
+        })
+    }
+}
+
+impl TryFrom<ProtoDatabaseChangeEvent> for DatabaseChangeEvent {
+    type Error = RipelError;
+
+    fn try_from(change: ProtoDatabaseChangeEvent) -> Result<Self> {
+        let base_event = change
+            .base_event
+            .ok_or_else(|| RipelError::ProcessingError("Base event is missing".to_string()))?;
+        let operation = ProtoOperationType::try_from(change.operation).map_err(|_| {
+            RipelError::ProcessingError(format!("Unknown operation type: {}", change.operation))
+        })?;
+
+        Ok(Self {
+            base_event: RipelEvent::try_from(base_event)?,
+            operation: OperationType::try_from(operation)?,
+            database: change.database,
+            table: change.table,
+            before: change.before.map(struct_to_json),
+            after: change.after.map(struct_to_json),
+            transaction_id: Some(change.transaction_id).filter(|id| !id.is_empty()),
+            lsn: Some(change.lsn).filter(|lsn| *lsn != 0),
+        })
+    }
+}
+
+fn to_timestamp(timestamp: DateTime<Utc>) -> Timestamp {
+    Timestamp {
+        seconds: timestamp.timestamp(),
+        nanos: timestamp.timestamp_subsec_nanos() as i32,
+    }
+}
+
+fn from_timestamp(timestamp: &Timestamp) -> Result<DateTime<Utc>> {
+    u32::try_from(timestamp.nanos)
+        .ok()
+        .and_then(|nanos| DateTime::from_timestamp(timestamp.seconds, nanos))
+        .ok_or_else(|| {
+            RipelError::ProcessingError(format!("Invalid event timestamp: {}", timestamp))
+        })
+}
+
+/// Convert a JSON object to a protobuf `Struct`; null maps to no struct
+fn json_to_struct(value: serde_json::Value) -> Result<Option<Struct>> {
+    match value {
+        serde_json::Value::Null => Ok(None),
+        serde_json::Value::Object(map) => Ok(Some(Struct {
+            fields: map
+                .into_iter()
+                .map(|(key, value)| (key, json_to_value(value)))
+                .collect(),
+        })),
+        other => Err(RipelError::ProcessingError(format!(
+            "Event data must be a JSON object, got: {}",
+            other
+        ))),
+    }
+}
+
+/// Convert a JSON value to a protobuf `Value`. Protobuf numbers are doubles,
+/// so integers beyond 2^53 lose precision.
+fn json_to_value(value: serde_json::Value) -> Value {
+    let kind = match value {
+        serde_json::Value::Null => Kind::NullValue(0),
+        serde_json::Value::Bool(b) => Kind::BoolValue(b),
+        serde_json::Value::Number(n) => Kind::NumberValue(n.as_f64().unwrap_or_default()),
+        serde_json::Value::String(s) => Kind::StringValue(s),
+        serde_json::Value::Array(items) => Kind::ListValue(ListValue {
+            values: items.into_iter().map(json_to_value).collect(),
+        }),
+        serde_json::Value::Object(map) => Kind::StructValue(Struct {
+            fields: map
+                .into_iter()
+                .map(|(key, value)| (key, json_to_value(value)))
+                .collect(),
+        }),
+    };
+
+    Value { kind: Some(kind) }
+}
+
+fn struct_to_json(value: Struct) -> serde_json::Value {
+    serde_json::Value::Object(
+        value
+            .fields
+            .into_iter()
+            .map(|(key, value)| (key, value_to_json(value)))
+            .collect(),
+    )
+}
+
+fn value_to_json(value: Value) -> serde_json::Value {
+    match value.kind {
+        None | Some(Kind::NullValue(_)) => serde_json::Value::Null,
+        Some(Kind::BoolValue(b)) => serde_json::Value::Bool(b),
+        Some(Kind::NumberValue(n)) => number_to_json(n),
+        Some(Kind::StringValue(s)) => serde_json::Value::String(s),
+        Some(Kind::ListValue(list)) => {
+            serde_json::Value::Array(list.values.into_iter().map(value_to_json).collect())
+        }
+        Some(Kind::StructValue(value)) => struct_to_json(value),
+    }
+}
+
+/// Whole doubles that fit exactly come back as JSON integers, so `{"id": 1}`
+/// survives a round trip unchanged. NaN and infinities become null.
+fn number_to_json(n: f64) -> serde_json::Value {
+    const MAX_EXACT: f64 = 9_007_199_254_740_992.0; // 2^53
+
+    if n.fract() == 0.0 && n.abs() <= MAX_EXACT {
+        serde_json::Value::from(n as i64)
+    } else {
+        serde_json::Number::from_f64(n).map_or(serde_json::Value::Null, serde_json::Value::Number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+    use serde_json::json;
+
+    #[test]
+    fn test_event_round_trip() {
+        let event = RipelEvent::new(
+            "user.created",
+            "user-service",
+            json!({"id": 1, "score": 4.5, "tags": ["a", null], "active": true, "address": {"city": "Madrid"}}),
+        )
+        .with_metadata("tenant", "acme")
+        .with_partition_key("user-1")
+        .with_schema_version(2);
+
+        let bytes = ProtoEvent::try_from(event.clone()).unwrap().encode_to_vec();
+        let decoded = RipelEvent::try_from(ProtoEvent::decode(bytes.as_slice()).unwrap()).unwrap();
+
+        assert_eq!(decoded, event);
+    }
+
+    #[test]
+    fn test_event_defaults_from_proto() {
+        let proto = ProtoEvent {
+            id: "id-1".to_string(),
+            timestamp: Some(to_timestamp(Utc::now())),
+            ..Default::default()
+        };
+
+        let event = RipelEvent::try_from(proto.clone()).unwrap();
+        assert_eq!(event.schema_version, 1);
+        assert_eq!(event.partition_key, None);
+        assert_eq!(event.data, serde_json::Value::Null);
+
+        let missing_timestamp = ProtoEvent {
+            timestamp: None,
+            ..proto
+        };
+        assert!(RipelEvent::try_from(missing_timestamp).is_err());
+    }
+
+    #[test]
+    fn test_non_object_data_rejected() {
+        let event = RipelEvent::new("t", "s", json!([1, 2]));
+        assert!(ProtoEvent::try_from(event).is_err());
+    }
+
+    #[test]
+    fn test_database_change_event_round_trip() {
+        let change = DatabaseChangeEvent::new(
+            OperationType::Update,
+            "shop",
+            "orders",
+            Some(json!({"id": 7, "status": "new"})),
+            Some(json!({"id": 7, "status": "paid"})),
+        )
+        .with_transaction_id("tx-1")
+        .with_lsn(42);
+
+        let proto = ProtoDatabaseChangeEvent::try_from(change.clone()).unwrap();
+        assert_eq!(proto.operation, ProtoOperationType::Update as i32);
+
+        let bytes = proto.encode_to_vec();
+        let decoded = DatabaseChangeEvent::try_from(
+            ProtoDatabaseChangeEvent::decode(bytes.as_slice()).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(decoded.base_event, change.base_event);
+        assert_eq!(decoded.operation, change.operation);
+        assert_eq!(decoded.database, change.database);
+        assert_eq!(decoded.table, change.table);
+        assert_eq!(decoded.before, change.before);
+        assert_eq!(decoded.after, change.after);
+        assert_eq!(decoded.transaction_id, change.transaction_id);
+        assert_eq!(decoded.lsn, change.lsn);
+    }
+
+    #[test]
+    fn test_unspecified_operation_rejected() {
+        assert!(OperationType::try_from(ProtoOperationType::Unspecified).is_err());
+
+        let change = ProtoDatabaseChangeEvent {
+            base_event: Some(ProtoEvent::try_from(RipelEvent::new("t", "s", json!({}))).unwrap()),
+            operation: 99,
+            ..Default::default()
+        };
+        assert!(DatabaseChangeEvent::try_from(change).is_err());
+    }
+}
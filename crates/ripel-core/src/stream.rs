@@ -2,12 +2,15 @@
 
 use crate::{RipelEvent, Result, RipelError};
 use async_trait::async_trait;
-use futures::stream;
-use futures::{Stream, StreamExt};
+use futures::{stream, Stream, StreamExt};
+use ripel_shared::EventMetrics;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use tokio::sync::broadcast;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
 use tokio_stream::wrappers::BroadcastStream;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 /// Event stream trait for abstracting different event sources
 #[async_trait]
@@ -22,16 +25,62 @@ pub trait EventStream: Send + Sync {
     async fn stop(&self) -> Result<()>;
 }
 
-/// In-memory event stream using broadcast channel
+/// What a subscriber does when it falls so far behind that the broadcast
+/// channel overwrote events it had not received yet
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LagPolicy {
+    /// Count the skipped events and keep going
+    #[default]
+    Continue,
+    /// End the subscriber's stream once it has skipped more than
+    /// `threshold` events in total.
+    ///
+    /// The stream simply returns `None`, like a stream that has run out of
+    /// events. Callers tell the two apart with
+    /// [`InMemoryEventStream::lag_failures`]; each failure is also logged as
+    /// an error and counted in the `ripel_stream_lag_exceeded_total` metric.
+    Fail { threshold: u64 },
+}
+
+/// In-memory event stream using broadcast channel.
+///
+/// Slow subscribers lose the oldest events once `capacity` is exceeded;
+/// [`InMemoryEventStream::lagged_count`] and the
+/// `ripel_stream_lagged_events_total` metric report how many were lost.
 pub struct InMemoryEventStream {
     tx: broadcast::Sender<RipelEvent>,
     _rx: broadcast::Receiver<RipelEvent>,
+    lag_policy: LagPolicy,
+    lagged: Arc<AtomicU64>,
+    lag_failures: Arc<AtomicU64>,
 }
 
 impl InMemoryEventStream {
     pub fn new(capacity: usize) -> Self {
         let (tx, rx) = broadcast::channel(capacity);
-        Self { tx, _rx: rx }
+        Self {
+            tx,
+            _rx: rx,
+            lag_policy: LagPolicy::default(),
+            lagged: Arc::new(AtomicU64::new(0)),
+            lag_failures: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Set what subscribers do when they lag behind
+    pub fn with_lag_policy(mut self, policy: LagPolicy) -> Self {
+        self.lag_policy = policy;
+        self
+    }
+
+    /// Total number of events skipped by lagging subscribers
+    pub fn lagged_count(&self) -> u64 {
+        self.lagged.load(Ordering::Relaxed)
+    }
+
+    /// Number of subscriber streams ended by [`LagPolicy::Fail`]
+    pub fn lag_failures(&self) -> u64 {
+        self.lag_failures.load(Ordering::Relaxed)
     }
 
     /// Publish an event to the stream
@@ -53,15 +102,38 @@ impl EventStream for InMemoryEventStream {
     async fn events(&self) -> Result<Pin<Box<dyn Stream<Item = RipelEvent> + Send>>> {
         let rx = self.tx.subscribe();
         let stream = BroadcastStream::new(rx);
-        let stream = StreamExt::filter_map(stream, |result| async move {
-            match result {
-                Ok(event) => Some(event),
-                Err(e) => {
-                    error!("Broadcast stream error: {}", e);
-                    None
+        let lag_policy = self.lag_policy;
+        let lagged = self.lagged.clone();
+        let lag_failures = self.lag_failures.clone();
+        let mut subscriber_lagged = 0;
+
+        // Ok(None) marks skipped events; Err ends the stream
+        let stream = StreamExt::map(stream, move |result| match result {
+            Ok(event) => Ok(Some(event)),
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                lagged.fetch_add(skipped, Ordering::Relaxed);
+                EventMetrics::stream_lagged_events(skipped);
+                subscriber_lagged += skipped;
+                warn!(skipped = skipped, "Subscriber lagged behind the broadcast stream");
+
+                match lag_policy {
+                    LagPolicy::Fail { threshold } if subscriber_lagged > threshold => {
+                        lag_failures.fetch_add(1, Ordering::Relaxed);
+                        EventMetrics::stream_lag_exceeded();
+                        error!(
+                            lagged = subscriber_lagged,
+                            threshold = threshold,
+                            "Subscriber lag exceeded threshold, ending stream"
+                        );
+                        Err(())
+                    }
+                    _ => Ok(None),
                 }
             }
         });
+        let stream = StreamExt::take_while(stream, |result| futures::future::ready(result.is_ok()));
+        let stream =
+            StreamExt::filter_map(stream, |result| futures::future::ready(result.ok().flatten()));
         let stream = StreamExt::boxed(stream);
         Ok(stream)
     }
@@ -141,23 +213,61 @@ impl EventStream for EventStreamMultiplexer {
     }
 }
 
-/// Simple event stream filter - simplified version to avoid lifetime issues
+/// Predicate deciding whether an event passes a [`FilteredEventStream`]
+pub type EventPredicate = Arc<dyn Fn(&RipelEvent) -> bool + Send + Sync>;
+
+/// Event stream that only yields events matching a predicate
 pub struct FilteredEventStream {
     inner: Box<dyn EventStream>,
+    predicate: EventPredicate,
+    metrics: Arc<Mutex<StreamMetrics>>,
 }
 
 impl FilteredEventStream {
-    pub fn new(inner: Box<dyn EventStream>) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: Box<dyn EventStream>,
+        predicate: impl Fn(&RipelEvent) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            inner,
+            predicate: Arc::new(predicate),
+            metrics: Arc::new(Mutex::new(StreamMetrics::default())),
+        }
+    }
+
+    /// Keep only events of `event_type`
+    pub fn by_event_type(inner: Box<dyn EventStream>, event_type: impl Into<String>) -> Self {
+        let event_type = event_type.into();
+        Self::new(inner, move |event| event.event_type == event_type)
+    }
+
+    /// Keep only events from `source`
+    pub fn by_source(inner: Box<dyn EventStream>, source: impl Into<String>) -> Self {
+        let source = source.into();
+        Self::new(inner, move |event| event.source == source)
+    }
+
+    /// Metrics with the number of events dropped by the predicate
+    pub fn get_metrics(&self) -> StreamMetrics {
+        self.metrics.lock().unwrap().clone()
     }
 }
 
 #[async_trait]
 impl EventStream for FilteredEventStream {
     async fn events(&self) -> Result<Pin<Box<dyn Stream<Item = RipelEvent> + Send>>> {
-        // For now, just pass through all events
-        // In a real implementation, you'd add filtering logic here
-        self.inner.events().await
+        let events = self.inner.events().await?;
+        let predicate = self.predicate.clone();
+        let metrics = self.metrics.clone();
+
+        let stream = StreamExt::filter(events, move |event| {
+            let keep = predicate(event);
+            if !keep {
+                metrics.lock().unwrap().increment_filtered();
+            }
+            futures::future::ready(keep)
+        });
+        Ok(StreamExt::boxed(stream))
     }
 
     async fn start(&self) -> Result<()> {
@@ -218,17 +328,20 @@ impl MetricsEventStream {
 #[async_trait]
 impl EventStream for MetricsEventStream {
     async fn events(&self) -> Result<Pin<Box<dyn Stream<Item = RipelEvent> + Send>>> {
-        let events = self.inner.events().await?;
+        let events = match self.inner.events().await {
+            Ok(events) => events,
+            Err(e) => {
+                self.metrics.lock().unwrap().increment_errors();
+                return Err(e);
+            }
+        };
         let metrics = self.metrics.clone();
         
         let stream = StreamExt::map(events, move |event| {
             {
                 let mut m = metrics.lock().unwrap();
                 m.increment_processed();
-                // Estimate event size
-                if let Ok(json) = serde_json::to_string(&event) {
-                    m.add_bytes(json.len() as u64);
-                }
+                m.add_bytes(event.approximate_size() as u64);
             }
             event
         });
@@ -276,13 +389,83 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_lagging_subscriber_continues() {
+        let stream = InMemoryEventStream::new(2);
+        let mut events = stream.events().await.unwrap();
+
+        // The slow consumer reads nothing until all events are published
+        let sent: Vec<_> = (0..5)
+            .map(|i| RipelEvent::new("test", "source", json!({"seq": i})))
+            .collect();
+        for event in &sent {
+            stream.publish(event.clone()).unwrap();
+        }
+
+        // The three oldest events were overwritten
+        assert_eq!(StreamExt::next(&mut events).await.unwrap().id, sent[3].id);
+        assert_eq!(StreamExt::next(&mut events).await.unwrap().id, sent[4].id);
+        assert_eq!(stream.lagged_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_lagging_subscriber_fails_over_threshold() {
+        let stream = InMemoryEventStream::new(2).with_lag_policy(LagPolicy::Fail { threshold: 2 });
+        let mut events = stream.events().await.unwrap();
+
+        for i in 0..5 {
+            stream.publish(RipelEvent::new("test", "source", json!({"seq": i}))).unwrap();
+        }
+
+        assert_eq!(stream.lag_failures(), 0);
+        assert!(StreamExt::next(&mut events).await.is_none());
+        assert_eq!(stream.lagged_count(), 3);
+        assert_eq!(stream.lag_failures(), 1);
+    }
+
     #[tokio::test]
     async fn test_filtered_stream() {
-        let base_stream = InMemoryEventStream::new(10);
-        let _filtered_stream = FilteredEventStream::new(Box::new(base_stream));
-        
-        // Test structure - passes through events without filtering for now
-        assert!(true); // Just verify it compiles
+        let base_stream = std::sync::Arc::new(InMemoryEventStream::new(10));
+        let filtered_stream = FilteredEventStream::by_event_type(
+            Box::new(SharedStream(base_stream.clone())),
+            "user.created",
+        );
+        let mut events = filtered_stream.events().await.unwrap();
+
+        let wanted = RipelEvent::new("user.created", "user-service", json!({"id": 1}));
+        let other = RipelEvent::new("order.placed", "order-service", json!({}));
+        let also_wanted = RipelEvent::new("user.created", "admin-service", json!({"id": 2}));
+        for event in [&wanted, &other, &also_wanted] {
+            base_stream.publish(event.clone()).unwrap();
+        }
+
+        assert_eq!(StreamExt::next(&mut events).await.unwrap().id, wanted.id);
+        assert_eq!(StreamExt::next(&mut events).await.unwrap().id, also_wanted.id);
+        assert_eq!(filtered_stream.get_metrics().events_filtered, 1);
+
+        // Nothing else arrives
+        base_stream.publish(other).unwrap();
+        tokio::select! {
+            _ = StreamExt::next(&mut events) => panic!("Filtered event was delivered"),
+            _ = sleep(Duration::from_millis(50)) => {}
+        }
+    }
+
+    #[tokio::test]
+    async fn test_filtered_stream_by_source() {
+        let base_stream = std::sync::Arc::new(InMemoryEventStream::new(10));
+        let filtered_stream = FilteredEventStream::by_source(
+            Box::new(SharedStream(base_stream.clone())),
+            "auth-service",
+        );
+        let mut events = filtered_stream.events().await.unwrap();
+
+        let login = RipelEvent::new("login", "auth-service", json!({}));
+        base_stream.publish(RipelEvent::new("login", "other-service", json!({}))).unwrap();
+        base_stream.publish(login.clone()).unwrap();
+
+        assert_eq!(StreamExt::next(&mut events).await.unwrap().id, login.id);
+        assert_eq!(filtered_stream.get_metrics().events_filtered, 1);
     }
 
     #[tokio::test]
@@ -295,4 +478,74 @@ mod tests {
         let initial_metrics = metrics_stream.get_metrics();
         assert_eq!(initial_metrics.events_processed, 0);
     }
-}
\ No newline at end of file
+
+    struct FailingEventStream;
+
+    #[async_trait]
+    impl EventStream for FailingEventStream {
+        async fn events(&self) -> Result<Pin<Box<dyn Stream<Item = RipelEvent> + Send>>> {
+            Err(RipelError::StreamError("source unavailable".into()))
+        }
+
+        async fn start(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn stop(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_errors() {
+        let metrics_stream = MetricsEventStream::new(Box::new(FailingEventStream));
+
+        assert!(metrics_stream.events().await.is_err());
+        assert!(metrics_stream.events().await.is_err());
+
+        let metrics = metrics_stream.get_metrics();
+        assert_eq!(metrics.processing_errors, 2);
+        assert_eq!(metrics.events_processed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_events_and_bytes() {
+        let base_stream = std::sync::Arc::new(InMemoryEventStream::new(10));
+        let metrics_stream = MetricsEventStream::new(Box::new(SharedStream(base_stream.clone())));
+        let mut events = metrics_stream.events().await.unwrap();
+
+        let sent = vec![
+            RipelEvent::new("test", "source", json!({"index": 1})),
+            RipelEvent::new("test", "source", json!({"name": "second"})),
+        ];
+        for event in &sent {
+            base_stream.publish(event.clone()).unwrap();
+        }
+        for _ in 0..sent.len() {
+            StreamExt::next(&mut events).await.unwrap();
+        }
+
+        let metrics = metrics_stream.get_metrics();
+        let expected_bytes: usize = sent.iter().map(RipelEvent::approximate_size).sum();
+        assert_eq!(metrics.events_processed, 2);
+        assert_eq!(metrics.bytes_processed, expected_bytes as u64);
+        assert_eq!(metrics.processing_errors, 0);
+    }
+
+    struct SharedStream(std::sync::Arc<InMemoryEventStream>);
+
+    #[async_trait]
+    impl EventStream for SharedStream {
+        async fn events(&self) -> Result<Pin<Box<dyn Stream<Item = RipelEvent> + Send>>> {
+            self.0.events().await
+        }
+
+        async fn start(&self) -> Result<()> {
+            self.0.start().await
+        }
+
+        async fn stop(&self) -> Result<()> {
+            self.0.stop().await
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Kafka topic administration
+
+use crate::TopicConfig;
+use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, TopicReplication};
+use rdkafka::client::DefaultClientContext;
+use rdkafka::config::ClientConfig;
+use rdkafka::error::RDKafkaErrorCode;
+use ripel_core::{Result, RipelError};
+use std::time::Duration;
+use tracing::{debug, info, instrument};
+
+impl TopicConfig {
+    /// Build the admin API topic specification for this configuration
+    pub fn to_new_topic(&self) -> NewTopic<'_> {
+        let mut new_topic = NewTopic::new(
+            &self.name,
+            self.partitions as i32,
+            TopicReplication::Fixed(self.replication_factor as i32),
+        );
+
+        for (key, value) in &self.config {
+            new_topic = new_topic.set(key, value);
+        }
+
+        new_topic
+    }
+}
+
+/// Admin helper for creating topics from [`TopicConfig`]s
+pub struct KafkaTopicAdmin {
+    admin: AdminClient<DefaultClientContext>,
+    operation_timeout: Duration,
+}
+
+impl KafkaTopicAdmin {
+    /// Create a new admin client
+    pub fn new(brokers: &[String]) -> Result<Self> {
+        let admin: AdminClient<DefaultClientContext> = ClientConfig::new()
+            .set("bootstrap.servers", brokers.join(","))
+            .create()
+            .map_err(|e| RipelError::kafka("Failed to create admin client", e))?;
+
+        Ok(Self {
+            admin,
+            operation_timeout: Duration::from_secs(30),
+        })
+    }
+
+    /// Set how long the broker may take to complete each admin operation
+    pub fn with_operation_timeout(mut self, timeout: Duration) -> Self {
+        self.operation_timeout = timeout;
+        self
+    }
+
+    /// Create any missing topics with their configured settings.
+    ///
+    /// Topics that already exist are left untouched, so this is safe to call on
+    /// every startup. Returns the names of the topics that were created.
+    #[instrument(skip(self, topics), fields(topic_count = topics.len()))]
+    pub async fn create_topics(&self, topics: &[TopicConfig]) -> Result<Vec<String>> {
+        let new_topics: Vec<NewTopic<'_>> = topics.iter().map(TopicConfig::to_new_topic).collect();
+        let options = AdminOptions::new().operation_timeout(Some(self.operation_timeout));
+
+        let results = self
+            .admin
+            .create_topics(&new_topics, &options)
+            .await
+            .map_err(|e| RipelError::kafka("Failed to create topics", e))?;
+
+        let mut created = Vec::new();
+        for result in results {
+            match result {
+                Ok(topic) => {
+                    info!(topic = %topic, "Created Kafka topic");
+                    created.push(topic);
+                }
+                Err((topic, RDKafkaErrorCode::TopicAlreadyExists)) => {
+                    debug!(topic = %topic, "Kafka topic already exists");
+                }
+                Err((topic, code)) => {
+                    return Err(RipelError::kafka(format!("Failed to create topic {}", topic), code));
+                }
+            }
+        }
+
+        Ok(created)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topic_config_to_new_topic() {
+        let config = TopicConfig::new("orders")
+            .with_partitions(6)
+            .with_replication_factor(3)
+            .with_cleanup_policy("compact");
+
+        let new_topic = config.to_new_topic();
+
+        assert_eq!(new_topic.name, "orders");
+        assert_eq!(new_topic.num_partitions, 6);
+        assert!(matches!(new_topic.replication, TopicReplication::Fixed(3)));
+        assert_eq!(new_topic.config.len(), config.config.len());
+        assert!(new_topic.config.contains(&("cleanup.policy", "compact")));
+        assert!(new_topic.config.contains(&("retention.ms", "604800000")));
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Kafka
+    async fn test_create_topics_is_idempotent() {
+        let admin = KafkaTopicAdmin::new(&["localhost:9092".to_string()]).unwrap();
+        let topics = vec![TopicConfig::new("ripel-admin-test").with_partitions(1)];
+
+        admin.create_topics(&topics).await.unwrap();
+
+        // Second call must not fail on the existing topic
+        let created = admin.create_topics(&topics).await.unwrap();
+        assert!(created.is_empty());
+    }
+}
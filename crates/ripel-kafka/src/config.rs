@@ -1,7 +1,29 @@
 //! Kafka-specific configuration
 
+use ripel_core::{Result, RipelError};
+use ripel_shared::resolve_secret;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Client config keys whose values may use `file:`/`env:` secret indirection
+const SECRET_CLIENT_CONFIG_KEYS: &[&str] = &[
+    "sasl.username",
+    "sasl.password",
+    "ssl.key.password",
+    "ssl.keystore.password",
+    "ssl.truststore.password",
+];
+
+/// Resolve a client config value, expanding secret references for credential keys
+pub fn resolve_client_config_value(key: &str, value: &str) -> Result<String> {
+    if SECRET_CLIENT_CONFIG_KEYS.contains(&key) {
+        resolve_secret(value).map_err(|e| RipelError::ConfigError(e.to_string()))
+    } else {
+        Ok(value.to_string())
+    }
+}
 
 /// Topic configuration for event routing
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,8 +75,14 @@ impl TopicConfig {
     }
 }
 
+/// Custom topic router, called with an event's type and source
+pub type RouterFn = dyn Fn(&str, &str) -> String + Send + Sync;
+
+/// Custom partitioner, called with an event's id, type and source
+pub type PartitionerFn = dyn Fn(&str, &str, &str) -> String + Send + Sync;
+
 /// Event routing configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct RoutingConfig {
     /// Default topic for events
     pub default_topic: String,
@@ -67,7 +95,18 @@ pub struct RoutingConfig {
     
     /// Custom routing function (not serializable)
     #[serde(skip)]
-    pub custom_router: Option<Box<dyn Fn(&str, &str) -> String + Send + Sync>>,
+    pub custom_router: Option<Arc<RouterFn>>,
+}
+
+impl fmt::Debug for RoutingConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RoutingConfig")
+            .field("default_topic", &self.default_topic)
+            .field("event_type_routing", &self.event_type_routing)
+            .field("source_routing", &self.source_routing)
+            .field("custom_router", &self.custom_router.as_ref().map(|_| "<fn>"))
+            .finish()
+    }
 }
 
 impl Default for RoutingConfig {
@@ -103,7 +142,7 @@ impl RoutingConfig {
     where
         F: Fn(&str, &str) -> String + Send + Sync + 'static,
     {
-        self.custom_router = Some(Box::new(router));
+        self.custom_router = Some(Arc::new(router));
         self
     }
 
@@ -130,12 +169,13 @@ impl RoutingConfig {
 }
 
 /// Partitioning strategy for events
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub enum PartitioningStrategy {
     /// Use event ID for partitioning
     EventId,
     
     /// Use partition key if available, otherwise event ID
+    #[default]
     PartitionKey,
     
     /// Use source system for partitioning
@@ -149,12 +189,19 @@ pub enum PartitioningStrategy {
     
     /// Custom partitioning function (not serializable)
     #[serde(skip)]
-    Custom(Box<dyn Fn(&str, &str, &str) -> String + Send + Sync>),
+    Custom(Arc<PartitionerFn>),
 }
 
-impl Default for PartitioningStrategy {
-    fn default() -> Self {
-        PartitioningStrategy::PartitionKey
+impl fmt::Debug for PartitioningStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartitioningStrategy::EventId => f.write_str("EventId"),
+            PartitioningStrategy::PartitionKey => f.write_str("PartitionKey"),
+            PartitioningStrategy::Source => f.write_str("Source"),
+            PartitioningStrategy::EventType => f.write_str("EventType"),
+            PartitioningStrategy::RoundRobin => f.write_str("RoundRobin"),
+            PartitioningStrategy::Custom(_) => f.write_str("Custom(<fn>)"),
+        }
     }
 }
 
@@ -178,7 +225,8 @@ impl PartitioningStrategy {
     }
 }
 
-/// Schema registry configuration (for future use)
+/// Schema registry configuration; when enabled, events are published as Avro
+/// (requires the `schema-registry` feature)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SchemaRegistryConfig {
     pub enabled: bool,
@@ -188,6 +236,19 @@ pub struct SchemaRegistryConfig {
     pub schema_subject_strategy: String,
 }
 
+impl SchemaRegistryConfig {
+    /// Replace `file:`/`env:` references in the credentials with their values
+    pub fn resolve_secrets(&mut self) -> Result<()> {
+        let resolve = |value: &str| {
+            resolve_secret(value).map_err(|e| RipelError::ConfigError(e.to_string()))
+        };
+
+        self.username = self.username.as_deref().map(resolve).transpose()?;
+        self.password = self.password.as_deref().map(resolve).transpose()?;
+        Ok(())
+    }
+}
+
 impl Default for SchemaRegistryConfig {
     fn default() -> Self {
         Self {
@@ -262,4 +323,34 @@ mod tests {
         assert_eq!(config.get_topic("payment.processed", "payment-service"), "payment-topic");
         assert_eq!(config.get_topic("other.event", "other-source"), "misc-topic");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_client_config_secret_resolution() {
+        std::env::set_var("RIPEL_TEST_SASL_PASSWORD", "kafka-s3cret");
+
+        assert_eq!(
+            resolve_client_config_value("sasl.password", "env:RIPEL_TEST_SASL_PASSWORD").unwrap(),
+            "kafka-s3cret"
+        );
+        // Non-credential keys are passed through untouched
+        assert_eq!(
+            resolve_client_config_value("client.id", "env:RIPEL_TEST_SASL_PASSWORD").unwrap(),
+            "env:RIPEL_TEST_SASL_PASSWORD"
+        );
+    }
+
+    #[test]
+    fn test_schema_registry_secret_resolution() {
+        std::env::set_var("RIPEL_TEST_REGISTRY_PASSWORD", "registry-s3cret");
+
+        let mut config = SchemaRegistryConfig {
+            username: Some("ripel".to_string()),
+            password: Some("env:RIPEL_TEST_REGISTRY_PASSWORD".to_string()),
+            ..Default::default()
+        };
+        config.resolve_secrets().unwrap();
+
+        assert_eq!(config.username.as_deref(), Some("ripel"));
+        assert_eq!(config.password.as_deref(), Some("registry-s3cret"));
+    }
+}
@@ -0,0 +1,280 @@
+//! Kafka event consumption with at-least-once processing
+
+use crate::DLQHandler;
+use async_trait::async_trait;
+use futures::stream::{self, Stream, StreamExt};
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{CommitMode, Consumer, StreamConsumer};
+use rdkafka::error::KafkaError;
+use rdkafka::message::BorrowedMessage;
+use rdkafka::Message;
+use ripel_core::{EventProcessor, EventStream, RipelEvent, Result, RipelError};
+use ripel_shared::KafkaConsumerConfig;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+/// Kafka consumer feeding [`RipelEvent`]s through an [`EventProcessor`].
+///
+/// Offsets are committed only once a message has been processed, or once it
+/// has been handed to the DLQ: messages that cannot be decoded or whose
+/// processing fails are forwarded there so they do not block the partition.
+/// If the DLQ itself is unavailable the stream ends without committing, and
+/// the message is redelivered when consumption resumes. The stream also ends
+/// on fatal consumer errors; other receive errors are retried after a delay.
+///
+/// As an [`EventStream`] it yields every successfully processed event.
+pub struct KafkaEventConsumer {
+    consumer: Arc<StreamConsumer>,
+    topics: Vec<String>,
+    processor: Arc<dyn EventProcessor>,
+    dlq_handler: Arc<DLQHandler>,
+}
+
+/// Delay before polling again after a non-fatal receive error
+const RECV_ERROR_BACKOFF: Duration = Duration::from_secs(1);
+
+/// What to do with a consumed message
+enum Outcome {
+    /// Processed; commit and emit the event
+    Processed(Box<RipelEvent>),
+    /// Parked in the DLQ; commit and move on
+    DeadLettered,
+    /// Could not be parked; stop without committing
+    Abort,
+}
+
+impl KafkaEventConsumer {
+    /// Create a consumer for `topics`.
+    ///
+    /// Auto commit is always disabled, whatever `config.enable_auto_commit`
+    /// says, since offsets are committed after processing.
+    pub fn new(
+        brokers: &[String],
+        topics: &[String],
+        config: &KafkaConsumerConfig,
+        processor: Arc<dyn EventProcessor>,
+        dlq_handler: Arc<DLQHandler>,
+    ) -> Result<Self> {
+        if config.enable_auto_commit {
+            debug!("Ignoring enable_auto_commit; offsets are committed after processing");
+        }
+
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", brokers.join(","))
+            .set("group.id", &config.group_id)
+            .set("auto.offset.reset", &config.auto_offset_reset)
+            .set("session.timeout.ms", config.session_timeout_ms.to_string())
+            .set("enable.auto.commit", "false")
+            .create()
+            .map_err(|e| RipelError::kafka("Failed to create consumer", e))?;
+
+        Ok(Self {
+            consumer: Arc::new(consumer),
+            topics: topics.to_vec(),
+            processor,
+            dlq_handler,
+        })
+    }
+
+    async fn handle_message(
+        processor: &dyn EventProcessor,
+        dlq_handler: &DLQHandler,
+        message: &BorrowedMessage<'_>,
+    ) -> Outcome {
+        let source = format!(
+            "{}/{}/{}",
+            message.topic(),
+            message.partition(),
+            message.offset()
+        );
+
+        let event = match decode_event(message.payload()) {
+            Ok(event) => event,
+            Err(e) => {
+                warn!(source = %source, error = %e, "Failed to decode Kafka message");
+                let payload = message.payload().unwrap_or_default();
+                return match dlq_handler
+                    .handle_undecodable_message(message.key(), payload, &e, &source)
+                    .await
+                {
+                    Ok(()) => Outcome::DeadLettered,
+                    Err(_) => Outcome::Abort,
+                };
+            }
+        };
+
+        match processor.process(event.clone()).await {
+            Ok(()) => Outcome::Processed(Box::new(event)),
+            Err(e) => {
+                warn!(event_id = %event.id, error = %e, "Failed to process consumed event");
+                match dlq_handler
+                    .handle_failed_event(event, &e.display_chain(), "PROCESSING_ERROR", message.topic())
+                    .await
+                {
+                    Ok(()) => Outcome::DeadLettered,
+                    Err(_) => Outcome::Abort,
+                }
+            }
+        }
+    }
+}
+
+/// Decode a message payload into an event
+fn decode_event(payload: Option<&[u8]>) -> std::result::Result<RipelEvent, String> {
+    let payload = payload.ok_or_else(|| "Message has no payload".to_string())?;
+    serde_json::from_slice(payload).map_err(|e| format!("Invalid event payload: {}", e))
+}
+
+#[async_trait]
+impl EventStream for KafkaEventConsumer {
+    async fn events(&self) -> Result<Pin<Box<dyn Stream<Item = RipelEvent> + Send>>> {
+        let state = (
+            self.consumer.clone(),
+            self.processor.clone(),
+            self.dlq_handler.clone(),
+        );
+
+        let events = stream::unfold(state, |(consumer, processor, dlq_handler)| async move {
+            loop {
+                let message = match consumer.recv().await {
+                    Ok(message) => message,
+                    Err(e @ KafkaError::MessageConsumptionFatal(_)) => {
+                        error!(error = %e, "Stopping consumer: fatal Kafka consumer error");
+                        return None;
+                    }
+                    Err(e) => {
+                        warn!(error = %e, "Kafka consumer error");
+                        tokio::time::sleep(RECV_ERROR_BACKOFF).await;
+                        continue;
+                    }
+                };
+
+                let outcome = Self::handle_message(processor.as_ref(), &dlq_handler, &message).await;
+                let event = match outcome {
+                    Outcome::Processed(event) => Some(*event),
+                    Outcome::DeadLettered => None,
+                    Outcome::Abort => {
+                        error!(
+                            topic = message.topic(),
+                            partition = message.partition(),
+                            offset = message.offset(),
+                            "Stopping consumer: message could not be processed or dead-lettered"
+                        );
+                        return None;
+                    }
+                };
+
+                if let Err(e) = consumer.commit_message(&message, CommitMode::Async) {
+                    warn!(error = %e, "Failed to commit Kafka offset");
+                }
+                // The message borrows the consumer, which moves into the next state
+                drop(message);
+
+                if let Some(event) = event {
+                    return Some((event, (consumer, processor, dlq_handler)));
+                }
+            }
+        });
+
+        Ok(events.boxed())
+    }
+
+    async fn start(&self) -> Result<()> {
+        let topics: Vec<&str> = self.topics.iter().map(String::as_str).collect();
+        self.consumer
+            .subscribe(&topics)
+            .map_err(|e| RipelError::kafka("Failed to subscribe to topics", e))?;
+
+        info!(topics = ?self.topics, "Kafka event consumer started");
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<()> {
+        self.consumer.unsubscribe();
+        info!("Kafka event consumer stopped");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DLQConfig;
+    use rdkafka::producer::FutureProducer;
+    use serde_json::json;
+
+    #[test]
+    fn test_decode_event() {
+        let event = RipelEvent::new("user.created", "user-service", json!({"id": 1}));
+        let payload = serde_json::to_vec(&event).unwrap();
+
+        let decoded = decode_event(Some(&payload)).unwrap();
+        assert_eq!(decoded.id, event.id);
+
+        assert!(decode_event(None).is_err());
+        assert!(decode_event(Some(b"not json")).is_err());
+    }
+
+    struct NoopProcessor;
+
+    #[async_trait]
+    impl EventProcessor for NoopProcessor {
+        async fn process(&self, _event: RipelEvent) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Kafka
+    async fn test_consume_published_event() {
+        let brokers = vec!["localhost:9092".to_string()];
+        let producer: FutureProducer = ClientConfig::new()
+            .set("bootstrap.servers", brokers.join(","))
+            .create()
+            .unwrap();
+        let dlq_handler = Arc::new(DLQHandler::new(
+            DLQConfig {
+                topic: "ripel-consumer-test-dlq".to_string(),
+                max_retries: 0,
+                retry_delay: Duration::from_millis(0),
+            },
+            producer.clone(),
+        ));
+
+        let config = KafkaConsumerConfig {
+            group_id: "ripel-consumer-test".to_string(),
+            auto_offset_reset: "earliest".to_string(),
+            enable_auto_commit: false,
+            session_timeout_ms: 10000,
+            max_poll_records: 100,
+        };
+        let consumer = KafkaEventConsumer::new(
+            &brokers,
+            &["ripel-consumer-test".to_string()],
+            &config,
+            Arc::new(NoopProcessor),
+            dlq_handler,
+        )
+        .unwrap();
+        consumer.start().await.unwrap();
+
+        let event = RipelEvent::new("user.created", "user-service", json!({"id": 1}));
+        let payload = serde_json::to_vec(&event).unwrap();
+        producer
+            .send(
+                rdkafka::producer::FutureRecord::<(), _>::to("ripel-consumer-test").payload(&payload),
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+
+        let mut events = consumer.events().await.unwrap();
+        let received = tokio::time::timeout(Duration::from_secs(30), events.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(received.id, event.id);
+    }
+}
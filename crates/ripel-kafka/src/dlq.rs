@@ -1,11 +1,18 @@
 //! Dead Letter Queue handling for failed events
 
-use ripel_core::{DLQEvent, RipelEvent, Result, RipelError};
+use crate::EventPublisher;
+use async_trait::async_trait;
+use ripel_core::{DLQEvent, DeadLetterSink, RipelEvent, Result, RipelError};
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{CommitMode, Consumer, StreamConsumer};
 use rdkafka::producer::{FutureProducer, FutureRecord};
 use rdkafka::util::Timeout;
+use rdkafka::{Message, Offset, TopicPartitionList};
 use serde_json;
+use std::collections::{BTreeSet, HashMap};
+use std::future::Future;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tracing::{error, info, warn};
 
@@ -48,6 +55,12 @@ impl DLQHandler {
             failed_destination,
         );
 
+        self.handle_dlq_event(dlq_event).await
+    }
+
+    /// Send an already-built DLQ event, e.g. one carrying the number of
+    /// attempts made before giving up
+    pub async fn handle_dlq_event(&self, dlq_event: DLQEvent) -> Result<()> {
         self.send_to_dlq(dlq_event).await?;
         
         let count = self.dlq_counter.fetch_add(1, Ordering::Relaxed) + 1;
@@ -58,10 +71,28 @@ impl DLQHandler {
         Ok(())
     }
 
+    /// Forward a message that could not be decoded into an event.
+    ///
+    /// The raw message is wrapped in a [`DLQEvent`] like any other failure,
+    /// so the DLQ topic only ever holds DLQ events; see [`undecodable_dlq_event`].
+    pub async fn handle_undecodable_message(
+        &self,
+        key: Option<&[u8]>,
+        payload: &[u8],
+        error_message: &str,
+        source: &str,
+    ) -> Result<()> {
+        let dlq_event = undecodable_dlq_event(key, payload, error_message, source);
+        self.handle_dlq_event(dlq_event).await?;
+
+        warn!(source = %source, error = %error_message, "Undecodable message sent to DLQ");
+        Ok(())
+    }
+
     /// Send DLQ event to Kafka
     async fn send_to_dlq(&self, dlq_event: DLQEvent) -> Result<()> {
         let payload = serde_json::to_vec(&dlq_event)
-            .map_err(|e| RipelError::SerializationError(e))?;
+            .map_err(RipelError::SerializationError)?;
 
         let key = dlq_event.original_event.id.clone();
         
@@ -86,7 +117,7 @@ impl DLQHandler {
                     kafka_error = %kafka_error,
                     "Failed to send event to DLQ - event will be lost!"
                 );
-                Err(RipelError::KafkaError(format!("DLQ send failed: {}", kafka_error)))
+                Err(RipelError::kafka("DLQ send failed", kafka_error))
             }
         }
     }
@@ -97,6 +128,50 @@ impl DLQHandler {
     }
 }
 
+#[async_trait]
+impl DeadLetterSink for DLQHandler {
+    async fn send(&self, dlq_event: DLQEvent) -> Result<()> {
+        self.handle_dlq_event(dlq_event).await
+    }
+}
+
+/// Event type of the envelope wrapping messages that could not be decoded
+pub const UNDECODABLE_EVENT_TYPE: &str = "ripel.undecodable";
+
+/// Error code of DLQ events wrapping messages that could not be decoded
+pub const UNDECODABLE_ERROR_CODE: &str = "UNDECODABLE_MESSAGE";
+
+/// Wrap a raw message that could not be decoded in a [`DLQEvent`].
+///
+/// The original event is a `ripel.undecodable` event from `source` whose data
+/// holds the key and payload: as a string when they are valid UTF-8, as an
+/// array of bytes otherwise.
+pub fn undecodable_dlq_event(
+    key: Option<&[u8]>,
+    payload: &[u8],
+    error_message: &str,
+    source: &str,
+) -> DLQEvent {
+    let data = serde_json::json!({
+        "key": key.map(raw_bytes_to_json),
+        "payload": raw_bytes_to_json(payload),
+    });
+
+    DLQEvent::new(
+        RipelEvent::new(UNDECODABLE_EVENT_TYPE, source, data),
+        error_message,
+        UNDECODABLE_ERROR_CODE,
+        source,
+    )
+}
+
+fn raw_bytes_to_json(bytes: &[u8]) -> serde_json::Value {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => serde_json::Value::from(text),
+        Err(_) => serde_json::Value::from(bytes.to_vec()),
+    }
+}
+
 /// DLQ event processor for handling and potentially retrying DLQ events
 pub struct DLQProcessor {
     handler: Arc<DLQHandler>,
@@ -107,6 +182,11 @@ impl DLQProcessor {
         Self { handler }
     }
 
+    /// Get the handler DLQ events are read from and re-sent through
+    pub fn handler(&self) -> &Arc<DLQHandler> {
+        &self.handler
+    }
+
     /// Process a DLQ event (e.g., for manual retry or analysis)
     pub async fn process_dlq_event(&self, dlq_event: DLQEvent) -> Result<()> {
         info!(
@@ -148,6 +228,242 @@ impl DLQProcessor {
     }
 }
 
+/// What to do with an event read back from the DLQ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DLQDecision {
+    /// Republish the original event through the publisher
+    Replay,
+    /// Drop the event for good
+    Discard,
+    /// Leave the event unacknowledged so a later drain sees it again.
+    /// Nothing at or after a skipped offset is committed on its partition,
+    /// so later events there are redelivered as well.
+    Skip,
+}
+
+/// Outcome of draining a DLQ
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DLQDrainReport {
+    pub replayed: u64,
+    pub discarded: u64,
+    pub skipped: u64,
+}
+
+/// Source of DLQ events for reprocessing
+#[async_trait]
+pub trait DLQSource: Send + Sync {
+    /// Receive the next DLQ event, or `None` once the queue is drained
+    async fn next_event(&self) -> Result<Option<DLQEvent>>;
+
+    /// Acknowledge the most recently received event as handled
+    async fn acknowledge(&self) -> Result<()>;
+}
+
+/// DLQ source reading [`DLQEvent`]s from a Kafka topic.
+///
+/// A partition's offset is only committed up to its lowest unacknowledged
+/// message. Skipped events and malformed messages are never acknowledged, so
+/// they hold the committed offset back and are redelivered, together with
+/// everything after them on that partition, when the group next consumes.
+pub struct KafkaDLQSource {
+    consumer: StreamConsumer,
+    idle_timeout: Duration,
+    last_received: Mutex<Option<(String, i32, i64)>>,
+    pending: Mutex<PendingOffsets>,
+}
+
+/// Unacknowledged offsets per partition, used to decide how far a commit may go
+#[derive(Debug, Default)]
+struct PendingOffsets {
+    partitions: HashMap<(String, i32), PartitionOffsets>,
+}
+
+#[derive(Debug)]
+struct PartitionOffsets {
+    unacknowledged: BTreeSet<i64>,
+    next: i64,
+    committed: i64,
+}
+
+impl PendingOffsets {
+    fn received(&mut self, topic: &str, partition: i32, offset: i64) {
+        let offsets = self
+            .partitions
+            .entry((topic.to_string(), partition))
+            .or_insert(PartitionOffsets {
+                unacknowledged: BTreeSet::new(),
+                next: offset,
+                committed: offset,
+            });
+        offsets.unacknowledged.insert(offset);
+        offsets.next = offsets.next.max(offset + 1);
+    }
+
+    /// Mark `offset` handled and return the offset to commit, if it advanced
+    fn acknowledged(&mut self, topic: &str, partition: i32, offset: i64) -> Option<i64> {
+        let offsets = self.partitions.get_mut(&(topic.to_string(), partition))?;
+        offsets.unacknowledged.remove(&offset);
+
+        let commit = offsets.unacknowledged.first().copied().unwrap_or(offsets.next);
+        if commit <= offsets.committed {
+            return None;
+        }
+        offsets.committed = commit;
+        Some(commit)
+    }
+}
+
+impl KafkaDLQSource {
+    /// Subscribe to `topic` as part of consumer group `group_id`
+    pub fn new(brokers: &[String], group_id: &str, topic: &str) -> Result<Self> {
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", brokers.join(","))
+            .set("group.id", group_id)
+            .set("enable.auto.commit", "false")
+            .set("auto.offset.reset", "earliest")
+            .create()
+            .map_err(|e| RipelError::kafka("Failed to create DLQ consumer", e))?;
+
+        consumer
+            .subscribe(&[topic])
+            .map_err(|e| RipelError::kafka("Failed to subscribe to DLQ topic", e))?;
+
+        Ok(Self {
+            consumer,
+            idle_timeout: Duration::from_secs(5),
+            last_received: Mutex::new(None),
+            pending: Mutex::new(PendingOffsets::default()),
+        })
+    }
+
+    /// Treat the DLQ as drained once no message arrives within `timeout`
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+}
+
+#[async_trait]
+impl DLQSource for KafkaDLQSource {
+    async fn next_event(&self) -> Result<Option<DLQEvent>> {
+        loop {
+            let message = match tokio::time::timeout(self.idle_timeout, self.consumer.recv()).await {
+                Ok(message) => message.map_err(|e| RipelError::kafka("Failed to read DLQ", e))?,
+                Err(_) => return Ok(None),
+            };
+
+            let position = (message.topic().to_string(), message.partition(), message.offset());
+            self.pending
+                .lock()
+                .unwrap()
+                .received(&position.0, position.1, position.2);
+            match serde_json::from_slice::<DLQEvent>(message.payload().unwrap_or_default()) {
+                Ok(dlq_event) => {
+                    *self.last_received.lock().unwrap() = Some(position);
+                    return Ok(Some(dlq_event));
+                }
+                Err(e) => {
+                    warn!(
+                        topic = %position.0,
+                        partition = position.1,
+                        offset = position.2,
+                        error = %e,
+                        "Skipping malformed DLQ message, leaving it uncommitted"
+                    );
+                }
+            }
+        }
+    }
+
+    async fn acknowledge(&self) -> Result<()> {
+        let Some((topic, partition, offset)) = self.last_received.lock().unwrap().take() else {
+            return Ok(());
+        };
+        let Some(commit) = self
+            .pending
+            .lock()
+            .unwrap()
+            .acknowledged(&topic, partition, offset)
+        else {
+            return Ok(());
+        };
+
+        let mut offsets = TopicPartitionList::new();
+        offsets
+            .add_partition_offset(&topic, partition, Offset::Offset(commit))
+            .map_err(|e| RipelError::kafka("Invalid DLQ offset", e))?;
+
+        self.consumer
+            .commit(&offsets, CommitMode::Async)
+            .map_err(|e| RipelError::kafka("Failed to commit DLQ offset", e))
+    }
+}
+
+/// Drains a DLQ, letting a callback decide whether each event is replayed,
+/// discarded or skipped
+pub struct DLQConsumer<S: DLQSource> {
+    source: S,
+    publisher: Arc<dyn EventPublisher>,
+}
+
+impl<S: DLQSource> DLQConsumer<S> {
+    pub fn new(source: S, publisher: Arc<dyn EventPublisher>) -> Self {
+        Self { source, publisher }
+    }
+
+    /// Process DLQ events until the source is drained.
+    ///
+    /// Replayed events are only acknowledged once republished successfully;
+    /// a failed replay stops the drain and leaves the event on the DLQ.
+    pub async fn drain<F, Fut>(&self, mut decide: F) -> Result<DLQDrainReport>
+    where
+        F: FnMut(DLQEvent) -> Fut,
+        Fut: Future<Output = DLQDecision>,
+    {
+        let mut report = DLQDrainReport::default();
+
+        while let Some(dlq_event) = self.source.next_event().await? {
+            let event_id = dlq_event.original_event.id.clone();
+            let original_event = dlq_event.original_event.clone();
+
+            match decide(dlq_event).await {
+                DLQDecision::Replay => {
+                    let result = self.publisher.publish(original_event).await?;
+                    if !result.success {
+                        return Err(RipelError::KafkaError {
+                            message: format!(
+                                "Replay of event {} failed: {}",
+                                event_id,
+                                result.error.unwrap_or_default()
+                            ),
+                            source: None,
+                        });
+                    }
+                    self.source.acknowledge().await?;
+                    report.replayed += 1;
+                    info!(event_id = %event_id, topic = %result.topic, "Replayed DLQ event");
+                }
+                DLQDecision::Discard => {
+                    self.source.acknowledge().await?;
+                    report.discarded += 1;
+                    info!(event_id = %event_id, "Discarded DLQ event");
+                }
+                DLQDecision::Skip => {
+                    report.skipped += 1;
+                }
+            }
+        }
+
+        info!(
+            replayed = report.replayed,
+            discarded = report.discarded,
+            skipped = report.skipped,
+            "DLQ drained"
+        );
+        Ok(report)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -207,4 +523,133 @@ mod tests {
         assert_eq!(dlq.error_code, "TEST_ERROR");
         assert_eq!(dlq.retry_count, 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_undecodable_message_envelope() {
+        let dlq_event =
+            undecodable_dlq_event(Some(b"key-1"), &[0xff, 0x00], "Invalid event payload", "t/0/7");
+
+        // The envelope reads back as a regular DLQ event
+        let bytes = serde_json::to_vec(&dlq_event).unwrap();
+        let decoded: DLQEvent = serde_json::from_slice(&bytes).unwrap();
+
+        assert_eq!(decoded.error_code, UNDECODABLE_ERROR_CODE);
+        assert_eq!(decoded.error_message, "Invalid event payload");
+        assert_eq!(decoded.failed_destination, "t/0/7");
+        assert_eq!(decoded.original_event.event_type, UNDECODABLE_EVENT_TYPE);
+        assert_eq!(decoded.original_event.data, json!({"key": "key-1", "payload": [255, 0]}));
+    }
+
+    #[test]
+    fn test_skip_then_replay_does_not_commit_past_skipped() {
+        let mut pending = PendingOffsets::default();
+
+        // Offset 10 is skipped, offset 11 is replayed on the same partition
+        pending.received("dlq", 0, 10);
+        pending.received("dlq", 0, 11);
+        assert_eq!(pending.acknowledged("dlq", 0, 11), None);
+
+        // Other partitions are unaffected
+        pending.received("dlq", 1, 5);
+        assert_eq!(pending.acknowledged("dlq", 1, 5), Some(6));
+
+        // Once the skipped offset is handled the commit catches up
+        pending.received("dlq", 0, 12);
+        assert_eq!(pending.acknowledged("dlq", 0, 10), Some(12));
+        assert_eq!(pending.acknowledged("dlq", 0, 12), Some(13));
+    }
+
+    struct FakeDLQSource {
+        events: Mutex<std::collections::VecDeque<DLQEvent>>,
+        acknowledged: AtomicU64,
+    }
+
+    #[async_trait]
+    impl DLQSource for FakeDLQSource {
+        async fn next_event(&self) -> Result<Option<DLQEvent>> {
+            Ok(self.events.lock().unwrap().pop_front())
+        }
+
+        async fn acknowledge(&self) -> Result<()> {
+            self.acknowledged.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+    }
+
+    struct FakePublisher {
+        published: Mutex<Vec<RipelEvent>>,
+    }
+
+    #[async_trait]
+    impl EventPublisher for FakePublisher {
+        async fn publish(&self, event: RipelEvent) -> Result<crate::PublishResult> {
+            let id = event.id.clone();
+            self.published.lock().unwrap().push(event);
+            Ok(crate::PublishResult::success(id, "replay-topic".to_string(), 0, 0))
+        }
+
+        async fn publish_batch(&self, events: Vec<RipelEvent>) -> Result<Vec<crate::PublishResult>> {
+            let mut results = Vec::new();
+            for event in events {
+                results.push(self.publish(event).await?);
+            }
+            Ok(results)
+        }
+
+        async fn start(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn stop(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dlq_consumer_decisions() {
+        let dlq_events: Vec<_> = ["REPLAY", "DISCARD", "SKIP"]
+            .iter()
+            .map(|code| {
+                DLQEvent::new(RipelEvent::new("test", "source", json!({})), "failed", *code, "topic")
+            })
+            .collect();
+        let replayed_id = dlq_events[0].original_event.id.clone();
+
+        let source = FakeDLQSource {
+            events: Mutex::new(dlq_events.into_iter().collect()),
+            acknowledged: AtomicU64::new(0),
+        };
+        let publisher = Arc::new(FakePublisher {
+            published: Mutex::new(Vec::new()),
+        });
+        let consumer = DLQConsumer::new(source, publisher.clone());
+
+        let report = consumer
+            .drain(|dlq_event| async move {
+                match dlq_event.error_code.as_str() {
+                    "REPLAY" => DLQDecision::Replay,
+                    "DISCARD" => DLQDecision::Discard,
+                    _ => DLQDecision::Skip,
+                }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            report,
+            DLQDrainReport {
+                replayed: 1,
+                discarded: 1,
+                skipped: 1,
+            }
+        );
+
+        // Only the replayed event was republished
+        let published = publisher.published.lock().unwrap();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].id, replayed_id);
+
+        // Replayed and discarded events were acknowledged, the skipped one was not
+        assert_eq!(consumer.source.acknowledged.load(Ordering::Relaxed), 2);
+    }
+}
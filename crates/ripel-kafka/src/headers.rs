@@ -0,0 +1,140 @@
+//! Mapping between event metadata and Kafka message headers
+
+use rdkafka::message::{Header, Headers, OwnedHeaders};
+use ripel_core::RipelEvent;
+use std::collections::HashMap;
+
+/// Header carrying the event's correlation id
+pub const CORRELATION_ID_HEADER: &str = "ripel.correlation_id";
+
+/// Header carrying the event type
+pub const EVENT_TYPE_HEADER: &str = "ripel.event_type";
+
+/// Header carrying the payload schema version
+pub const SCHEMA_VERSION_HEADER: &str = "ripel.schema_version";
+
+/// Prefix reserved for headers set by RIPeL itself; metadata entries using it
+/// are not restored by [`EventHeaders::from_headers`]
+pub const RESERVED_HEADER_PREFIX: &str = "ripel.";
+
+/// Event context carried in Kafka headers alongside the payload
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventHeaders {
+    pub correlation_id: Option<String>,
+    pub event_type: Option<String>,
+    pub schema_version: Option<u32>,
+    pub metadata: HashMap<String, String>,
+}
+
+impl EventHeaders {
+    /// Collect the correlation id, event type, schema version and metadata
+    /// of `event`
+    pub fn from_event(event: &RipelEvent) -> Self {
+        Self {
+            correlation_id: Some(event.correlation_id.clone()),
+            event_type: Some(event.event_type.clone()),
+            schema_version: Some(event.schema_version),
+            metadata: event.metadata.clone(),
+        }
+    }
+
+    /// Rebuild the event context from message headers. Headers that are not
+    /// valid UTF-8, and schema versions that are not numbers, are skipped.
+    pub fn from_headers<H: Headers>(headers: &H) -> Self {
+        let mut result = Self::default();
+
+        for header in headers.iter() {
+            let Some(value) = header.value.and_then(|v| std::str::from_utf8(v).ok()) else {
+                continue;
+            };
+
+            match header.key {
+                CORRELATION_ID_HEADER => result.correlation_id = Some(value.to_string()),
+                EVENT_TYPE_HEADER => result.event_type = Some(value.to_string()),
+                SCHEMA_VERSION_HEADER => result.schema_version = value.parse().ok(),
+                key if key.starts_with(RESERVED_HEADER_PREFIX) => {}
+                key => {
+                    result.metadata.insert(key.to_string(), value.to_string());
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Build the headers to attach to a Kafka record
+    pub fn to_owned_headers(&self) -> OwnedHeaders {
+        let mut headers = OwnedHeaders::new_with_capacity(self.metadata.len() + 3);
+
+        if let Some(correlation_id) = &self.correlation_id {
+            headers = headers.insert(Header {
+                key: CORRELATION_ID_HEADER,
+                value: Some(correlation_id),
+            });
+        }
+        if let Some(event_type) = &self.event_type {
+            headers = headers.insert(Header {
+                key: EVENT_TYPE_HEADER,
+                value: Some(event_type),
+            });
+        }
+        if let Some(schema_version) = self.schema_version {
+            headers = headers.insert(Header {
+                key: SCHEMA_VERSION_HEADER,
+                value: Some(&schema_version.to_string()),
+            });
+        }
+        for (key, value) in &self.metadata {
+            headers = headers.insert(Header {
+                key,
+                value: Some(value),
+            });
+        }
+
+        headers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_headers_populated_from_event() {
+        let event = RipelEvent::new("user.created", "user-service", json!({}))
+            .with_correlation_id("corr-123")
+            .with_schema_version(2)
+            .with_metadata("trace_id", "abc")
+            .with_metadata("tenant", "acme");
+
+        let headers = EventHeaders::from_event(&event).to_owned_headers();
+        let pairs: HashMap<_, _> = headers
+            .iter()
+            .map(|header| (header.key, header.value.unwrap()))
+            .collect();
+
+        assert_eq!(headers.count(), 5);
+        assert_eq!(pairs[CORRELATION_ID_HEADER], b"corr-123");
+        assert_eq!(pairs[EVENT_TYPE_HEADER], b"user.created");
+        assert_eq!(pairs[SCHEMA_VERSION_HEADER], b"2");
+        assert_eq!(pairs["trace_id"], b"abc");
+        assert_eq!(pairs["tenant"], b"acme");
+    }
+
+    #[test]
+    fn test_headers_round_trip() {
+        let event = RipelEvent::new("user.created", "user-service", json!({}))
+            .with_metadata("trace_id", "abc");
+
+        let headers = EventHeaders::from_event(&event)
+            .to_owned_headers()
+            .insert(Header {
+                key: "ripel.error",
+                value: Some("ignored"),
+            });
+
+        let restored = EventHeaders::from_headers(&headers);
+        assert_eq!(restored, EventHeaders::from_event(&event));
+    }
+}
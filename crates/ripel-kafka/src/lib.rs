@@ -1,28 +1,38 @@
 //! Kafka publishing with DLQ support for RIPeL
 
-use ripel_core::{DLQEvent, RipelEvent, Result, RipelError};
-use ripel_shared::{EventMetrics, PerfTimer, RetryExecutor, RetryPolicy};
+use ripel_core::{DLQEvent, DeadLetterSink, RipelEvent, Result, RipelError};
+use ripel_shared::{EventMetrics, HealthAggregator, PerfTimer};
 use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
 use rdkafka::config::ClientConfig;
 use rdkafka::producer::{FutureProducer, FutureRecord};
 use rdkafka::util::Timeout;
-use rdkafka::Message;
-use serde_json;
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tracing::{error, info, instrument, warn};
 
+pub mod admin;
 pub mod config;
+pub mod consumer;
 pub mod dlq;
+pub mod headers;
+pub mod health;
 pub mod producer;
 pub mod publisher;
+pub mod serializer;
 
+pub use admin::*;
 pub use config::*;
+pub use consumer::*;
 pub use dlq::*;
+pub use headers::*;
+pub use health::*;
 pub use producer::*;
 pub use publisher::*;
+pub use serializer::*;
 
 /// Kafka publishing configuration
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -52,6 +62,19 @@ pub struct KafkaPublisherConfig {
     
     /// Compression
     pub compression_type: String,
+
+    /// Publish Avro through a Schema Registry instead of JSON when enabled
+    #[serde(default)]
+    pub schema_registry: SchemaRegistryConfig,
+
+    /// Largest serialized payload that is sent to Kafka; keep it below the
+    /// broker's `message.max.bytes`. Larger events are dead-lettered.
+    #[serde(default = "default_max_payload_bytes")]
+    pub max_payload_bytes: usize,
+}
+
+fn default_max_payload_bytes() -> usize {
+    1_000_000
 }
 
 impl Default for KafkaPublisherConfig {
@@ -76,6 +99,8 @@ impl Default for KafkaPublisherConfig {
             batch_size: 100,
             batch_timeout_ms: 100,
             compression_type: "snappy".to_string(),
+            schema_registry: SchemaRegistryConfig::default(),
+            max_payload_bytes: default_max_payload_bytes(),
         }
     }
 }
@@ -135,7 +160,9 @@ impl PublishResult {
 pub struct KafkaEventPublisher {
     config: KafkaPublisherConfig,
     producer: FutureProducer,
-    dlq_handler: Arc<DLQHandler>,
+    dlq: Arc<dyn DeadLetterSink>,
+    routing: Option<RoutingConfig>,
+    serializer: Arc<dyn Serializer>,
 }
 
 impl KafkaEventPublisher {
@@ -147,12 +174,12 @@ impl KafkaEventPublisher {
 
         // Apply additional producer configuration
         for (key, value) in &config.producer_config {
-            client_config.set(key, value);
+            client_config.set(key, resolve_client_config_value(key, value)?);
         }
 
         let producer: FutureProducer = client_config
             .create()
-            .map_err(|e| RipelError::KafkaError(format!("Failed to create producer: {}", e)))?;
+            .map_err(|e| RipelError::kafka("Failed to create producer", e))?;
 
         let dlq_config = DLQConfig {
             topic: config.dlq_topic.clone(),
@@ -160,26 +187,117 @@ impl KafkaEventPublisher {
             retry_delay: Duration::from_millis(config.retry_delay_ms),
         };
         
-        let dlq_handler = Arc::new(DLQHandler::new(dlq_config, producer.clone()));
+        let dlq = Arc::new(DLQHandler::new(dlq_config, producer.clone()));
+        let serializer = Self::serializer_for(&config)?;
 
         Ok(Self {
             config,
             producer,
-            dlq_handler,
+            dlq,
+            routing: None,
+            serializer,
         })
     }
 
-    /// Get topic for event (uses routing logic)
-    fn get_topic_for_event(&self, _event: &RipelEvent) -> String {
-        // In a real implementation, you might have routing rules
-        // For now, use the default topic
-        self.config.default_topic.clone()
+    /// Pick the payload serializer selected by `config`
+    fn serializer_for(config: &KafkaPublisherConfig) -> Result<Arc<dyn Serializer>> {
+        if !config.schema_registry.enabled {
+            return Ok(Arc::new(JsonSerializer));
+        }
+
+        #[cfg(feature = "schema-registry")]
+        {
+            let mut registry = config.schema_registry.clone();
+            registry.resolve_secrets()?;
+            Ok(Arc::new(AvroSerializer::new(registry)?))
+        }
+
+        #[cfg(not(feature = "schema-registry"))]
+        Err(RipelError::ConfigError(
+            "Schema registry serialization requires the `schema-registry` feature".to_string(),
+        ))
+    }
+
+    /// Serialize payloads with `serializer` instead of the configured one
+    pub fn with_serializer(mut self, serializer: Arc<dyn Serializer>) -> Self {
+        self.serializer = serializer;
+        self
+    }
+
+    /// Send failed events to `sink` instead of the configured DLQ topic
+    pub fn with_dead_letter_sink(mut self, sink: Arc<dyn DeadLetterSink>) -> Self {
+        self.dlq = sink;
+        self
+    }
+
+    /// Pick each event's topic with `routing` instead of always using the
+    /// default topic
+    pub fn with_routing(mut self, routing: RoutingConfig) -> Self {
+        self.routing = Some(routing);
+        self
+    }
+
+    /// Register a producer liveness check with `aggregator`. Registering
+    /// here rather than in `start` keeps restarts from adding duplicate checks.
+    pub fn with_health_aggregator(self, aggregator: Arc<HealthAggregator>) -> Self {
+        aggregator.register_async(Box::new(KafkaHealthCheck::new(
+            "kafka-producer",
+            self.producer.clone(),
+        )));
+        self
+    }
+
+    /// Get topic for event: a `target_topic` chosen upstream wins, then the
+    /// routing rules, then the default topic
+    fn get_topic_for_event(&self, event: &RipelEvent) -> String {
+        if let Some(topic) = event.metadata.get("target_topic") {
+            return topic.clone();
+        }
+
+        match &self.routing {
+            Some(routing) => routing.get_topic(&event.event_type, &event.source),
+            None => self.config.default_topic.clone(),
+        }
     }
 
-    /// Serialize event for Kafka
-    fn serialize_event(&self, event: &RipelEvent) -> Result<Vec<u8>> {
-        serde_json::to_vec(event)
-            .map_err(|e| RipelError::SerializationError(e))
+    /// Serialize event for Kafka, rejecting payloads over `max_payload_bytes`
+    async fn serialize_event(&self, topic: &str, event: &RipelEvent) -> Result<Vec<u8>> {
+        let payload = self.serializer.serialize(topic, event).await?;
+        if payload.len() > self.config.max_payload_bytes {
+            return Err(RipelError::PayloadTooLarge {
+                size: payload.len(),
+                limit: self.config.max_payload_bytes,
+            });
+        }
+        Ok(payload)
+    }
+
+    /// Dead-letter an event whose payload is too large to publish. The DLQ
+    /// copy drops `data`, which would be just as oversized on the DLQ topic;
+    /// the error message records the original size.
+    async fn reject_oversized(
+        &self,
+        mut event: RipelEvent,
+        topic: String,
+        error: RipelError,
+    ) -> PublishResult {
+        warn!(event_id = %event.id, error = %error, "Event payload too large to publish");
+        EventMetrics::kafka_operation("publish", &topic, false);
+
+        let event_id = event.id.clone();
+        let error_message = error.to_string();
+        event.data = serde_json::Value::Null;
+
+        let dlq_event = DLQEvent::new(event, &error_message, "PAYLOAD_TOO_LARGE", &topic);
+        if let Err(dlq_error) = self.dlq.send(dlq_event).await {
+            error!(
+                event_id = %event_id,
+                dlq_error = %dlq_error,
+                "Failed to send event to DLQ"
+            );
+        }
+
+        PublishResult::failure(event_id, topic, error_message)
     }
 }
 
@@ -187,16 +305,23 @@ impl KafkaEventPublisher {
 impl EventPublisher for KafkaEventPublisher {
     #[instrument(skip(self, event), fields(event_id = %event.id, event_type = %event.event_type))]
     async fn publish(&self, event: RipelEvent) -> Result<PublishResult> {
+        let topic = self.get_topic_for_event(&event);
         let _timer = PerfTimer::new("kafka_publish_duration")
-            .with_label("topic", &self.config.default_topic);
+            .with_label("topic", &topic);
 
-        let topic = self.get_topic_for_event(&event);
-        let payload = self.serialize_event(&event)?;
+        let payload = match self.serialize_event(&topic, &event).await {
+            Ok(payload) => payload,
+            Err(e @ RipelError::PayloadTooLarge { .. }) => {
+                return Ok(self.reject_oversized(event, topic, e).await);
+            }
+            Err(e) => return Err(e),
+        };
         let key = event.effective_partition_key().to_string();
         
         let record = FutureRecord::to(&topic)
             .key(&key)
-            .payload(&payload);
+            .payload(&payload)
+            .headers(EventHeaders::from_event(&event).to_owned_headers());
 
         match self.producer.send(record, Timeout::After(Duration::from_secs(30))).await {
             Ok((partition, offset)) => {
@@ -213,12 +338,13 @@ impl EventPublisher for KafkaEventPublisher {
                 EventMetrics::kafka_operation("publish", &topic, false);
 
                 // Send to DLQ
-                if let Err(dlq_error) = self.dlq_handler.handle_failed_event(
+                let dlq_event = DLQEvent::new(
                     event.clone(),
-                    &kafka_error.to_string(),
+                    kafka_error.to_string(),
                     "KAFKA_PUBLISH_ERROR",
                     &topic,
-                ).await {
+                );
+                if let Err(dlq_error) = self.dlq.send(dlq_event).await {
                     error!(
                         event_id = %event.id,
                         dlq_error = %dlq_error,
@@ -233,18 +359,9 @@ impl EventPublisher for KafkaEventPublisher {
 
     async fn publish_batch(&self, events: Vec<RipelEvent>) -> Result<Vec<PublishResult>> {
         let _timer = PerfTimer::new("kafka_publish_batch_duration")
-            .with_label("batch_size", &events.len().to_string());
-
-        let mut results = Vec::with_capacity(events.len());
-        
-        // For better performance, you could use futures::stream::FuturesUnordered
-        // to publish events concurrently
-        for event in events {
-            let result = self.publish(event).await?;
-            results.push(result);
-        }
+            .with_label("batch_size", events.len().to_string());
 
-        Ok(results)
+        publish_by_partition_key(events, self.config.batch_size, |event| self.publish(event)).await
     }
 
     async fn start(&self) -> Result<()> {
@@ -254,6 +371,7 @@ impl EventPublisher for KafkaEventPublisher {
             default_topic = %self.config.default_topic,
             "Starting Kafka event publisher"
         );
+
         Ok(())
     }
 
@@ -263,12 +381,77 @@ impl EventPublisher for KafkaEventPublisher {
     }
 }
 
+/// Publish a batch with `publish`, running different partition keys
+/// concurrently while events sharing a key are sent one after another.
+///
+/// At most `max_in_flight` sends are outstanding at any time. Results are
+/// returned in the order of the input batch, one per event: an error returned
+/// by `publish` becomes a failed result for that event and the rest of the
+/// batch, including later events with the same key, is still published.
+pub async fn publish_by_partition_key<F, Fut>(
+    events: Vec<RipelEvent>,
+    max_in_flight: usize,
+    publish: F,
+) -> Result<Vec<PublishResult>>
+where
+    F: Fn(RipelEvent) -> Fut,
+    Fut: Future<Output = Result<PublishResult>>,
+{
+    let event_count = events.len();
+
+    // Group by partition key, keeping each key's events in submission order
+    let mut groups: Vec<Vec<(usize, RipelEvent)>> = Vec::new();
+    let mut group_by_key: HashMap<String, usize> = HashMap::new();
+    for (index, event) in events.into_iter().enumerate() {
+        let group = *group_by_key
+            .entry(event.effective_partition_key().to_string())
+            .or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+        groups[group].push((index, event));
+    }
+
+    let publish = &publish;
+    let group_results: Vec<_> = stream::iter(groups)
+        .map(|group| async move {
+            let mut results = Vec::with_capacity(group.len());
+            for (index, event) in group {
+                let event_id = event.id.clone();
+                let result = publish(event).await.unwrap_or_else(|e| {
+                    warn!(event_id = %event_id, error = %e, "Failed to publish event in batch");
+                    PublishResult::failure(event_id, String::new(), e.display_chain())
+                });
+                results.push((index, result));
+            }
+            results
+        })
+        .buffer_unordered(max_in_flight.max(1))
+        .collect()
+        .await;
+
+    let mut ordered = vec![None; event_count];
+    for results in group_results {
+        for (index, result) in results {
+            ordered[index] = Some(result);
+        }
+    }
+
+    Ok(ordered.into_iter().flatten().collect())
+}
+
+/// Event queued for the batch worker, with an optional channel for its result
+type QueuedEvent = (RipelEvent, Option<oneshot::Sender<PublishResult>>);
+
 /// Batching event publisher wrapper
+///
+/// `publish` and `publish_batch` wait until the worker has flushed the batch
+/// containing the events and return the inner publisher's results. Use
+/// [`BatchingEventPublisher::enqueue`] to submit without waiting.
 pub struct BatchingEventPublisher {
     inner: Arc<dyn EventPublisher>,
-    event_tx: mpsc::Sender<RipelEvent>,
+    event_tx: mpsc::Sender<QueuedEvent>,
     batch_size: usize,
-    batch_timeout: Duration,
 }
 
 impl BatchingEventPublisher {
@@ -283,7 +466,6 @@ impl BatchingEventPublisher {
             inner: inner.clone(),
             event_tx,
             batch_size,
-            batch_timeout,
         };
 
         // Start batching worker
@@ -294,7 +476,7 @@ impl BatchingEventPublisher {
 
     async fn batch_worker(
         publisher: Arc<dyn EventPublisher>,
-        mut event_rx: mpsc::Receiver<RipelEvent>,
+        mut event_rx: mpsc::Receiver<QueuedEvent>,
         batch_size: usize,
         batch_timeout: Duration,
     ) {
@@ -309,9 +491,7 @@ impl BatchingEventPublisher {
                             batch.push(event);
                             
                             if batch.len() >= batch_size {
-                                if let Err(e) = publisher.publish_batch(std::mem::take(&mut batch)).await {
-                                    error!("Batch publish failed: {}", e);
-                                }
+                                Self::flush_batch(&publisher, std::mem::take(&mut batch)).await;
                             }
                         }
                         None => break, // Channel closed
@@ -319,9 +499,7 @@ impl BatchingEventPublisher {
                 }
                 _ = timeout.tick() => {
                     if !batch.is_empty() {
-                        if let Err(e) = publisher.publish_batch(std::mem::take(&mut batch)).await {
-                            error!("Batch publish failed: {}", e);
-                        }
+                        Self::flush_batch(&publisher, std::mem::take(&mut batch)).await;
                     }
                 }
             }
@@ -329,50 +507,108 @@ impl BatchingEventPublisher {
 
         // Flush remaining events
         if !batch.is_empty() {
-            if let Err(e) = publisher.publish_batch(batch).await {
-                error!("Final batch publish failed: {}", e);
+            Self::flush_batch(&publisher, batch).await;
+        }
+    }
+
+    /// Publish `batch` and hand each waiting caller the result for its event.
+    ///
+    /// Results are matched to callers by position, since `publish_batch`
+    /// returns them in input order and event ids need not be unique.
+    async fn flush_batch(publisher: &Arc<dyn EventPublisher>, batch: Vec<QueuedEvent>) {
+        let mut waiting = Vec::with_capacity(batch.len());
+        let mut events = Vec::with_capacity(batch.len());
+        for (event, reply) in batch {
+            waiting.push((event.id.clone(), reply));
+            events.push(event);
+        }
+
+        let results: Vec<std::result::Result<PublishResult, String>> =
+            match publisher.publish_batch(events).await {
+                Ok(results) => results.into_iter().map(Ok).collect(),
+                Err(e) => {
+                    error!("Batch publish failed: {}", e);
+                    vec![Err(e.to_string()); waiting.len()]
+                }
+            };
+        let mut results = results.into_iter();
+
+        for (event_id, reply) in waiting {
+            let result = match results.next() {
+                Some(Ok(result)) => result,
+                Some(Err(error)) => PublishResult::failure(event_id, String::new(), error),
+                None => PublishResult::failure(
+                    event_id,
+                    String::new(),
+                    "No result returned for event".to_string(),
+                ),
+            };
+            if let Some(reply) = reply {
+                // The caller may have stopped waiting
+                let _ = reply.send(result);
             }
         }
     }
 
-    /// Get sender for submitting events
+    /// Get a sender for submitting events without waiting for their results
+    #[deprecated(note = "use `enqueue`, or `submit` to receive the publish result")]
     pub fn sender(&self) -> mpsc::Sender<RipelEvent> {
-        self.event_tx.clone()
+        let (event_tx, mut event_rx) = mpsc::channel::<RipelEvent>(self.batch_size);
+        let queue = self.event_tx.clone();
+        tokio::spawn(async move {
+            while let Some(event) = event_rx.recv().await {
+                if queue.send((event, None)).await.is_err() {
+                    break;
+                }
+            }
+        });
+        event_tx
+    }
+
+    async fn queue(&self, event: RipelEvent, reply: Option<oneshot::Sender<PublishResult>>) -> Result<()> {
+        self.event_tx
+            .send((event, reply))
+            .await
+            .map_err(|_| RipelError::InternalError("Batch channel closed".to_string()))
+    }
+
+    /// Queue an event and return a receiver resolving to its result once the
+    /// batch containing it has been published
+    pub async fn submit(&self, event: RipelEvent) -> Result<oneshot::Receiver<PublishResult>> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.queue(event, Some(reply_tx)).await?;
+        Ok(reply_rx)
     }
+
+    /// Queue an event without waiting for its result; failures are only logged
+    pub async fn enqueue(&self, event: RipelEvent) -> Result<()> {
+        self.queue(event, None).await
+    }
+}
+
+async fn await_result(receiver: oneshot::Receiver<PublishResult>) -> Result<PublishResult> {
+    receiver
+        .await
+        .map_err(|_| RipelError::InternalError("Batch worker dropped the event".to_string()))
 }
 
 #[async_trait]
 impl EventPublisher for BatchingEventPublisher {
     async fn publish(&self, event: RipelEvent) -> Result<PublishResult> {
-        self.event_tx
-            .send(event.clone())
-            .await
-            .map_err(|_| RipelError::InternalError("Batch channel full".to_string()))?;
-
-        // Return optimistic result - actual result will be handled by batch worker
-        Ok(PublishResult::success(
-            event.id,
-            "batched".to_string(),
-            0,
-            0,
-        ))
+        await_result(self.submit(event).await?).await
     }
 
     async fn publish_batch(&self, events: Vec<RipelEvent>) -> Result<Vec<PublishResult>> {
-        for event in &events {
-            self.event_tx
-                .send(event.clone())
-                .await
-                .map_err(|_| RipelError::InternalError("Batch channel full".to_string()))?;
+        let mut receivers = Vec::with_capacity(events.len());
+        for event in events {
+            receivers.push(self.submit(event).await?);
         }
 
-        // Return optimistic results
-        Ok(events
-            .into_iter()
-            .map(|event| {
-                PublishResult::success(event.id, "batched".to_string(), 0, 0)
-            })
-            .collect())
+        let mut results = Vec::with_capacity(receivers.len());
+        for receiver in receivers {
+            results.push(await_result(receiver).await?);
+        }
+        Ok(results)
     }
 
     async fn start(&self) -> Result<()> {
@@ -420,4 +656,320 @@ mod tests {
         // This will fail without Kafka, but tests the config
         assert!(publisher.is_err() || publisher.is_ok());
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_publish_by_partition_key_ordering() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let events = vec![
+            RipelEvent::new("test", "source", json!({"seq": 1})).with_partition_key("a"),
+            RipelEvent::new("test", "source", json!({"seq": 1})).with_partition_key("b"),
+            RipelEvent::new("test", "source", json!({"seq": 2})).with_partition_key("a"),
+            RipelEvent::new("test", "source", json!({"seq": 2})).with_partition_key("b"),
+        ];
+        let event_ids: Vec<_> = events.iter().map(|event| event.id.clone()).collect();
+
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+        let published = std::sync::Mutex::new(Vec::new());
+
+        let results = publish_by_partition_key(events, 4, |event| {
+            let (in_flight, max_in_flight, published) = (&in_flight, &max_in_flight, &published);
+            async move {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(current, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                published.lock().unwrap().push((
+                    event.effective_partition_key().to_string(),
+                    event.data["seq"].as_i64().unwrap(),
+                ));
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(PublishResult::success(event.id, "test-topic".to_string(), 0, 0))
+            }
+        })
+        .await
+        .unwrap();
+
+        // Results follow the input order
+        let result_ids: Vec<_> = results.iter().map(|result| result.event_id.clone()).collect();
+        assert_eq!(result_ids, event_ids);
+
+        // Different keys were published concurrently
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+
+        // Events sharing a key kept their relative order
+        let published = published.into_inner().unwrap();
+        for key in ["a", "b"] {
+            let seqs: Vec<_> = published
+                .iter()
+                .filter(|(k, _)| k == key)
+                .map(|(_, seq)| *seq)
+                .collect();
+            assert_eq!(seqs, vec![1, 2]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_by_partition_key_concurrency_cap() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let events: Vec<_> = (0..6)
+            .map(|i| {
+                RipelEvent::new("test", "source", json!({"seq": i}))
+                    .with_partition_key(format!("key-{}", i))
+            })
+            .collect();
+        let event_ids: Vec<_> = events.iter().map(|event| event.id.clone()).collect();
+
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+
+        let results = publish_by_partition_key(events, 2, |event| {
+            let (in_flight, max_in_flight) = (&in_flight, &max_in_flight);
+            async move {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(current, Ordering::SeqCst);
+                // Later events finish first so completion order differs from input order
+                let seq = event.data["seq"].as_u64().unwrap();
+                tokio::time::sleep(Duration::from_millis(30 - 5 * seq)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(PublishResult::success(event.id, "test-topic".to_string(), 0, 0))
+            }
+        })
+        .await
+        .unwrap();
+
+        let result_ids: Vec<_> = results.iter().map(|result| result.event_id.clone()).collect();
+        assert_eq!(result_ids, event_ids);
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_publish_by_partition_key_reports_errors_per_event() {
+        let events = vec![
+            RipelEvent::new("test", "source", json!({})).with_partition_key("a"),
+            RipelEvent::new("poison", "source", json!({})).with_partition_key("a"),
+            RipelEvent::new("test", "source", json!({})).with_partition_key("a"),
+        ];
+        let event_ids: Vec<_> = events.iter().map(|event| event.id.clone()).collect();
+
+        let results = publish_by_partition_key(events, 4, |event| async move {
+            if event.event_type == "poison" {
+                return Err(RipelError::InternalError("boom".to_string()));
+            }
+            Ok(PublishResult::success(event.id, "test-topic".to_string(), 0, 0))
+        })
+        .await
+        .unwrap();
+
+        let result_ids: Vec<_> = results.iter().map(|result| result.event_id.clone()).collect();
+        assert_eq!(result_ids, event_ids);
+        let successes: Vec<_> = results.iter().map(|result| result.success).collect();
+        assert_eq!(successes, vec![true, false, true]);
+        assert!(results[1].error.as_deref().unwrap().contains("boom"));
+    }
+
+    #[test]
+    fn test_topic_selection_uses_routing() {
+        let routing = RoutingConfig::new("routed-default")
+            .route_by_event_type("user.created", "user-events")
+            .route_by_source("auth-service", "auth-events");
+        let publisher = KafkaEventPublisher::new(KafkaPublisherConfig::default())
+            .unwrap()
+            .with_routing(routing);
+
+        let user = RipelEvent::new("user.created", "user-service", json!({}));
+        let login = RipelEvent::new("login.attempted", "auth-service", json!({}));
+        let other = RipelEvent::new("order.placed", "order-service", json!({}));
+        let mut targeted = RipelEvent::new("user.created", "user-service", json!({}));
+        targeted.metadata.insert("target_topic".to_string(), "explicit-topic".to_string());
+
+        assert_eq!(publisher.get_topic_for_event(&user), "user-events");
+        assert_eq!(publisher.get_topic_for_event(&login), "auth-events");
+        assert_eq!(publisher.get_topic_for_event(&other), "routed-default");
+        assert_eq!(publisher.get_topic_for_event(&targeted), "explicit-topic");
+
+        // Without routing everything goes to the configured default topic
+        let publisher = KafkaEventPublisher::new(KafkaPublisherConfig::default()).unwrap();
+        assert_eq!(publisher.get_topic_for_event(&user), "ripel-events");
+    }
+
+    struct RecordingPublisher {
+        batches: std::sync::Mutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl EventPublisher for RecordingPublisher {
+        async fn publish(&self, event: RipelEvent) -> Result<PublishResult> {
+            if event.event_type == "poison" {
+                return Ok(PublishResult::failure(event.id, "test-topic".to_string(), "rejected".to_string()));
+            }
+            Ok(PublishResult::success(event.id, "test-topic".to_string(), 1, 42))
+        }
+
+        async fn publish_batch(&self, events: Vec<RipelEvent>) -> Result<Vec<PublishResult>> {
+            self.batches.lock().unwrap().push(events.len());
+            let mut results = Vec::new();
+            for event in events {
+                results.push(self.publish(event).await?);
+            }
+            Ok(results)
+        }
+
+        async fn start(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn stop(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batching_publisher_returns_real_results() {
+        let inner = Arc::new(RecordingPublisher {
+            batches: std::sync::Mutex::new(Vec::new()),
+        });
+        let publisher = BatchingEventPublisher::new(inner.clone(), 2, Duration::from_secs(60));
+
+        let good = RipelEvent::new("test", "source", json!({}));
+        let poison = RipelEvent::new("poison", "source", json!({}));
+        let good_rx = publisher.submit(good.clone()).await.unwrap();
+        let poison_rx = publisher.submit(poison.clone()).await.unwrap();
+
+        let good_result = good_rx.await.unwrap();
+        assert!(good_result.success);
+        assert_eq!(good_result.event_id, good.id);
+        assert_eq!(good_result.topic, "test-topic");
+        assert_eq!(good_result.offset, Some(42));
+
+        let poison_result = poison_rx.await.unwrap();
+        assert!(!poison_result.success);
+        assert_eq!(poison_result.event_id, poison.id);
+
+        // Both events went out in a single batch
+        assert_eq!(*inner.batches.lock().unwrap(), vec![2]);
+    }
+
+    #[tokio::test]
+    async fn test_batching_publisher_duplicate_event_ids() {
+        let inner = Arc::new(RecordingPublisher {
+            batches: std::sync::Mutex::new(Vec::new()),
+        });
+        let publisher = BatchingEventPublisher::new(inner.clone(), 2, Duration::from_secs(60));
+
+        // Same id twice: each caller still gets the result for its own event
+        let good = RipelEvent::new("test", "source", json!({}));
+        let mut poison = RipelEvent::new("poison", "source", json!({}));
+        poison.id = good.id.clone();
+        let good_rx = publisher.submit(good).await.unwrap();
+        let poison_rx = publisher.submit(poison).await.unwrap();
+
+        assert!(good_rx.await.unwrap().success);
+        assert!(!poison_rx.await.unwrap().success);
+    }
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn test_batching_publisher_sender() {
+        let inner = Arc::new(RecordingPublisher {
+            batches: std::sync::Mutex::new(Vec::new()),
+        });
+        let publisher = BatchingEventPublisher::new(inner.clone(), 2, Duration::from_millis(20));
+
+        let sender = publisher.sender();
+        sender.send(RipelEvent::new("test", "source", json!({}))).await.unwrap();
+        sender.send(RipelEvent::new("test", "source", json!({}))).await.unwrap();
+
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while inner.batches.lock().unwrap().iter().sum::<usize>() < 2 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_batching_publisher_publish_waits_for_flush() {
+        let inner = Arc::new(RecordingPublisher {
+            batches: std::sync::Mutex::new(Vec::new()),
+        });
+        let publisher = BatchingEventPublisher::new(inner.clone(), 10, Duration::from_millis(20));
+
+        publisher.enqueue(RipelEvent::new("test", "source", json!({}))).await.unwrap();
+        let event = RipelEvent::new("test", "source", json!({}));
+        let result = publisher.publish(event.clone()).await.unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.event_id, event.id);
+        assert_eq!(result.partition, Some(1));
+    }
+
+    #[derive(Default)]
+    struct RecordingSink {
+        events: std::sync::Mutex<Vec<DLQEvent>>,
+    }
+
+    #[async_trait]
+    impl DeadLetterSink for RecordingSink {
+        async fn send(&self, dlq_event: DLQEvent) -> Result<()> {
+            self.events.lock().unwrap().push(dlq_event);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oversized_payload_goes_to_dlq() {
+        let config = KafkaPublisherConfig {
+            brokers: vec!["127.0.0.1:1".to_string()],
+            max_payload_bytes: 512,
+            ..Default::default()
+        };
+        let sink = Arc::new(RecordingSink::default());
+        let publisher = KafkaEventPublisher::new(config)
+            .unwrap()
+            .with_dead_letter_sink(sink.clone());
+
+        let small = RipelEvent::new("test", "source", json!({"id": 1}));
+        assert!(publisher.serialize_event("events", &small).await.is_ok());
+
+        let large = RipelEvent::new("test", "source", json!({"blob": "x".repeat(1024)}));
+        let error = publisher.serialize_event("events", &large).await.unwrap_err();
+        assert!(matches!(error, RipelError::PayloadTooLarge { limit: 512, .. }));
+
+        let result = publisher.publish(large.clone()).await.unwrap();
+        assert!(!result.success);
+        assert_eq!(result.event_id, large.id);
+        assert!(result.error.unwrap().starts_with("Payload too large"));
+
+        let dead_lettered = sink.events.lock().unwrap();
+        assert_eq!(dead_lettered.len(), 1);
+        assert_eq!(dead_lettered[0].error_code, "PAYLOAD_TOO_LARGE");
+        assert_eq!(dead_lettered[0].original_event.id, large.id);
+        assert_eq!(dead_lettered[0].original_event.data, serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_health_check_registered_once() {
+        let aggregator = Arc::new(HealthAggregator::new());
+        let _publisher = KafkaEventPublisher::new(KafkaPublisherConfig::default())
+            .unwrap()
+            .with_health_aggregator(aggregator.clone());
+
+        assert_eq!(aggregator.check_names(), vec!["kafka-producer".to_string()]);
+    }
+
+    #[test]
+    fn test_schema_registry_requires_feature() {
+        let mut config = KafkaPublisherConfig::default();
+        config.schema_registry.enabled = true;
+
+        let result = KafkaEventPublisher::new(config);
+        if cfg!(feature = "schema-registry") {
+            assert!(result.is_ok());
+        } else {
+            assert!(matches!(result, Err(RipelError::ConfigError(_))));
+        }
+    }
+}
@@ -1,17 +1,21 @@
 //! Kafka producer configuration and management
 
 use rdkafka::config::ClientConfig;
-use rdkafka::producer::{FutureProducer, FutureRecord};
+use futures::future::join_all;
+use rdkafka::message::{Header, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord, Producer};
 use rdkafka::util::Timeout;
 use ripel_core::{Result, RipelError};
 use std::collections::HashMap;
 use std::time::Duration;
-use tracing::{info, instrument};
+use tracing::{info, instrument, warn};
 
 /// Kafka producer wrapper with enhanced configuration
 pub struct RipelKafkaProducer {
     producer: FutureProducer,
     config: KafkaProducerConfig,
+    /// Held from begin to commit/abort; librdkafka allows one open transaction
+    transaction_lock: tokio::sync::Mutex<()>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -27,6 +31,10 @@ pub struct KafkaProducerConfig {
     pub delivery_timeout_ms: u32,
     pub max_in_flight_requests: u32,
     pub enable_idempotence: bool,
+    /// Enables the transactional API when set. Transactions require
+    /// `enable_idempotence`, `acks = "all"` and at most 5 in-flight requests
+    #[serde(default)]
+    pub transactional_id: Option<String>,
     pub additional_config: HashMap<String, String>,
 }
 
@@ -44,6 +52,7 @@ impl Default for KafkaProducerConfig {
             delivery_timeout_ms: 120000,
             max_in_flight_requests: 5,
             enable_idempotence: true,
+            transactional_id: None,
             additional_config: HashMap::new(),
         }
     }
@@ -55,6 +64,36 @@ impl RipelKafkaProducer {
     pub fn new(config: KafkaProducerConfig) -> Result<Self> {
         info!("Creating Kafka producer");
 
+        let producer: FutureProducer = Self::client_config(&config)?
+            .create()
+            .map_err(|e| RipelError::kafka("Failed to create producer", e))?;
+
+        // Registers the transactional id with the coordinator; blocks until
+        // the broker answers
+        if config.transactional_id.is_some() {
+            producer
+                .init_transactions(Timeout::After(Duration::from_millis(config.request_timeout_ms as u64)))
+                .map_err(|e| RipelError::kafka("Failed to initialize transactions", e))?;
+        }
+
+        Ok(Self {
+            producer,
+            config,
+            transaction_lock: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Build the client configuration for `config`
+    fn client_config(config: &KafkaProducerConfig) -> Result<ClientConfig> {
+        if let Some(transactional_id) = &config.transactional_id {
+            if !config.enable_idempotence || config.acks != "all" || config.max_in_flight_requests > 5 {
+                return Err(RipelError::ConfigError(format!(
+                    "Transactional producer '{}' requires enable_idempotence, acks=all and at most 5 in-flight requests",
+                    transactional_id
+                )));
+            }
+        }
+
         let mut client_config = ClientConfig::new();
         
         // Basic configuration
@@ -62,24 +101,23 @@ impl RipelKafkaProducer {
         client_config.set("client.id", &config.client_id);
         client_config.set("compression.type", &config.compression_type);
         client_config.set("acks", &config.acks);
-        client_config.set("retries", &config.retries.to_string());
-        client_config.set("batch.size", &config.batch_size.to_string());
-        client_config.set("linger.ms", &config.linger_ms.to_string());
-        client_config.set("request.timeout.ms", &config.request_timeout_ms.to_string());
-        client_config.set("delivery.timeout.ms", &config.delivery_timeout_ms.to_string());
-        client_config.set("max.in.flight.requests.per.connection", &config.max_in_flight_requests.to_string());
-        client_config.set("enable.idempotence", &config.enable_idempotence.to_string());
+        client_config.set("retries", config.retries.to_string());
+        client_config.set("batch.size", config.batch_size.to_string());
+        client_config.set("linger.ms", config.linger_ms.to_string());
+        client_config.set("request.timeout.ms", config.request_timeout_ms.to_string());
+        client_config.set("delivery.timeout.ms", config.delivery_timeout_ms.to_string());
+        client_config.set("max.in.flight.requests.per.connection", config.max_in_flight_requests.to_string());
+        client_config.set("enable.idempotence", config.enable_idempotence.to_string());
+        if let Some(transactional_id) = &config.transactional_id {
+            client_config.set("transactional.id", transactional_id);
+        }
 
         // Additional configuration
         for (key, value) in &config.additional_config {
-            client_config.set(key, value);
+            client_config.set(key, crate::resolve_client_config_value(key, value)?);
         }
 
-        let producer: FutureProducer = client_config
-            .create()
-            .map_err(|e| RipelError::KafkaError(format!("Failed to create producer: {}", e)))?;
-
-        Ok(Self { producer, config })
+        Ok(client_config)
     }
 
     /// Send a message to Kafka
@@ -101,7 +139,7 @@ impl RipelKafkaProducer {
             .send(record, Timeout::After(timeout))
             .await
             .map_err(|(kafka_error, _record)| {
-                RipelError::KafkaError(format!("Send failed: {}", kafka_error))
+                RipelError::kafka("Send failed", kafka_error)
             })?;
 
         Ok(result)
@@ -116,14 +154,18 @@ impl RipelKafkaProducer {
         headers: &[(&str, &[u8])],
         timeout: Duration,
     ) -> Result<(i32, i64)> {
-        let mut record = FutureRecord::to(topic).payload(payload);
-        
-        if let Some(k) = key {
-            record = record.key(k);
+        let mut owned_headers = OwnedHeaders::new_with_capacity(headers.len());
+        for (header_key, header_value) in headers {
+            owned_headers = owned_headers.insert(Header {
+                key: header_key,
+                value: Some(*header_value),
+            });
         }
 
-        for (header_key, header_value) in headers {
-            record = record.header(header_key, header_value);
+        let mut record = FutureRecord::to(topic).payload(payload).headers(owned_headers);
+
+        if let Some(k) = key {
+            record = record.key(k);
         }
 
         let result = self
@@ -131,7 +173,7 @@ impl RipelKafkaProducer {
             .send(record, Timeout::After(timeout))
             .await
             .map_err(|(kafka_error, _record)| {
-                RipelError::KafkaError(format!("Send with headers failed: {}", kafka_error))
+                RipelError::kafka("Send with headers failed", kafka_error)
             })?;
 
         Ok(result)
@@ -141,15 +183,74 @@ impl RipelKafkaProducer {
     pub async fn flush(&self, timeout: Duration) -> Result<()> {
         self.producer
             .flush(Timeout::After(timeout))
-            .map_err(|e| RipelError::KafkaError(format!("Flush failed: {}", e)))
+            .map_err(|e| RipelError::kafka("Flush failed", e))
     }
 
-    /// Get producer statistics
-    pub fn get_statistics(&self) -> Result<String> {
+    /// Begin a transaction; callers must hold `transaction_lock`
+    fn begin_transaction(&self) -> Result<()> {
         self.producer
-            .context()
-            .statistics()
-            .map_err(|e| RipelError::KafkaError(format!("Failed to get statistics: {}", e)))
+            .begin_transaction()
+            .map_err(|e| RipelError::kafka("Failed to begin transaction", e))
+    }
+
+    /// Commit the current transaction, flushing outstanding messages first
+    async fn commit_transaction(&self, timeout: Duration) -> Result<()> {
+        let producer = self.producer.clone();
+        tokio::task::spawn_blocking(move || producer.commit_transaction(Timeout::After(timeout)))
+            .await
+            .map_err(|e| RipelError::InternalError(format!("Commit task failed: {}", e)))?
+            .map_err(|e| RipelError::kafka("Failed to commit transaction", e))
+    }
+
+    /// Abort the current transaction, discarding its messages
+    async fn abort_transaction(&self, timeout: Duration) -> Result<()> {
+        let producer = self.producer.clone();
+        tokio::task::spawn_blocking(move || producer.abort_transaction(Timeout::After(timeout)))
+            .await
+            .map_err(|e| RipelError::InternalError(format!("Abort task failed: {}", e)))?
+            .map_err(|e| RipelError::kafka("Failed to abort transaction", e))
+    }
+
+    /// Send `messages` to `topic` atomically: either all are committed or the
+    /// transaction is aborted and the first error returned. A failed commit
+    /// aborts the transaction as well, so the producer can begin a new one.
+    ///
+    /// Requires a `transactional_id`. Concurrent batches on the same producer
+    /// are serialized. Plain `send` calls are not: one made while a batch is
+    /// open joins its transaction and is committed or aborted with it.
+    pub async fn send_transactional_batch(
+        &self,
+        topic: &str,
+        messages: &[(Option<&str>, &[u8])],
+        timeout: Duration,
+    ) -> Result<Vec<(i32, i64)>> {
+        let _transaction = self.transaction_lock.lock().await;
+        self.begin_transaction()?;
+
+        let results = join_all(
+            messages
+                .iter()
+                .map(|(key, payload)| self.send(topic, *key, payload, timeout)),
+        )
+        .await;
+
+        match results.into_iter().collect::<Result<Vec<_>>>() {
+            Ok(positions) => match self.commit_transaction(timeout).await {
+                Ok(()) => Ok(positions),
+                Err(e) => {
+                    if let Err(abort_error) = self.abort_transaction(timeout).await {
+                        warn!(error = %abort_error, "Failed to abort transaction");
+                    }
+                    Err(e)
+                }
+            },
+            Err(e) => {
+                if let Err(abort_error) = self.abort_transaction(timeout).await {
+                    warn!(error = %abort_error, "Failed to abort transaction");
+                }
+                Err(e)
+            }
+        }
     }
 
     /// Get configuration
@@ -159,6 +260,10 @@ impl RipelKafkaProducer {
 }
 
 /// Producer pool for high-throughput scenarios
+///
+/// With a `transactional_id`, each pooled producer gets its own id suffixed
+/// with its index. Producers are handed out round-robin and may be shared, so
+/// avoid plain `send` on a producer while it runs a transactional batch.
 pub struct KafkaProducerPool {
     producers: Vec<RipelKafkaProducer>,
     current_index: std::sync::atomic::AtomicUsize,
@@ -170,10 +275,7 @@ impl KafkaProducerPool {
         let mut producers = Vec::with_capacity(pool_size);
         
         for i in 0..pool_size {
-            let mut producer_config = config.clone();
-            producer_config.client_id = format!("{}-{}", config.client_id, i);
-            
-            let producer = RipelKafkaProducer::new(producer_config)?;
+            let producer = RipelKafkaProducer::new(Self::producer_config(&config, i))?;
             producers.push(producer);
         }
 
@@ -183,6 +285,18 @@ impl KafkaProducerPool {
         })
     }
 
+    /// Configuration for the pooled producer at `index`; transactional ids must
+    /// be unique or each new producer fences the previous one
+    fn producer_config(config: &KafkaProducerConfig, index: usize) -> KafkaProducerConfig {
+        let mut producer_config = config.clone();
+        producer_config.client_id = format!("{}-{}", config.client_id, index);
+        producer_config.transactional_id = config
+            .transactional_id
+            .as_ref()
+            .map(|id| format!("{}-{}", id, index));
+        producer_config
+    }
+
     /// Get the next producer (round-robin)
     pub fn get_producer(&self) -> &RipelKafkaProducer {
         let index = self.current_index.fetch_add(1, std::sync::atomic::Ordering::Relaxed) % self.producers.len();
@@ -216,6 +330,70 @@ mod tests {
         assert!(config.enable_idempotence);
     }
 
+    #[test]
+    fn test_transactional_config_applied() {
+        let config = KafkaProducerConfig {
+            transactional_id: Some("ripel-cdc-0".to_string()),
+            ..Default::default()
+        };
+
+        let client_config = RipelKafkaProducer::client_config(&config).unwrap();
+        assert_eq!(client_config.get("transactional.id"), Some("ripel-cdc-0"));
+        assert_eq!(client_config.get("enable.idempotence"), Some("true"));
+        assert_eq!(client_config.get("acks"), Some("all"));
+
+        let non_transactional = RipelKafkaProducer::client_config(&KafkaProducerConfig::default()).unwrap();
+        assert_eq!(non_transactional.get("transactional.id"), None);
+    }
+
+    #[test]
+    fn test_transactional_config_requires_idempotence() {
+        let config = KafkaProducerConfig {
+            transactional_id: Some("ripel-cdc-0".to_string()),
+            enable_idempotence: false,
+            ..Default::default()
+        };
+        assert!(matches!(
+            RipelKafkaProducer::client_config(&config),
+            Err(RipelError::ConfigError(_))
+        ));
+
+        let config = KafkaProducerConfig {
+            transactional_id: Some("ripel-cdc-0".to_string()),
+            acks: "1".to_string(),
+            ..Default::default()
+        };
+        assert!(RipelKafkaProducer::client_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_producer_pool_unique_transactional_ids() {
+        let config = KafkaProducerConfig {
+            transactional_id: Some("ripel-cdc".to_string()),
+            ..Default::default()
+        };
+
+        let transactional_ids: Vec<_> = (0..3)
+            .map(|i| {
+                let client_config =
+                    RipelKafkaProducer::client_config(&KafkaProducerPool::producer_config(&config, i)).unwrap();
+                client_config.get("transactional.id").map(str::to_string)
+            })
+            .collect();
+        assert_eq!(
+            transactional_ids,
+            vec![
+                Some("ripel-cdc-0".to_string()),
+                Some("ripel-cdc-1".to_string()),
+                Some("ripel-cdc-2".to_string()),
+            ]
+        );
+
+        let non_transactional = KafkaProducerPool::producer_config(&KafkaProducerConfig::default(), 1);
+        assert_eq!(non_transactional.client_id, "ripel-producer-1");
+        assert_eq!(non_transactional.transactional_id, None);
+    }
+
     #[test]
     fn test_producer_creation() {
         let config = KafkaProducerConfig::default();
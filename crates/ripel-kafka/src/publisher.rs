@@ -32,8 +32,10 @@ impl RipelEventPublisher {
 
     /// Create a publisher with default configuration
     pub fn with_default_config(brokers: Vec<String>) -> Result<Self> {
-        let mut kafka_config = KafkaPublisherConfig::default();
-        kafka_config.brokers = brokers;
+        let kafka_config = KafkaPublisherConfig {
+            brokers,
+            ..Default::default()
+        };
 
         let routing_config = RoutingConfig::default();
         let partitioning_strategy = PartitioningStrategy::default();
@@ -0,0 +1,304 @@
+//! Event payload serialization
+
+use async_trait::async_trait;
+use ripel_core::{Result, RipelError, RipelEvent};
+
+/// Magic byte opening every Confluent wire-format message
+pub const CONFLUENT_MAGIC_BYTE: u8 = 0;
+
+/// Turns events into Kafka message payloads
+#[async_trait]
+pub trait Serializer: Send + Sync {
+    /// Serialize `event` for publishing to `topic`
+    async fn serialize(&self, topic: &str, event: &RipelEvent) -> Result<Vec<u8>>;
+}
+
+/// Serializes events as plain JSON
+#[derive(Debug, Clone, Default)]
+pub struct JsonSerializer;
+
+#[async_trait]
+impl Serializer for JsonSerializer {
+    async fn serialize(&self, _topic: &str, event: &RipelEvent) -> Result<Vec<u8>> {
+        serde_json::to_vec(event).map_err(RipelError::SerializationError)
+    }
+}
+
+/// Frame `payload` in the Confluent wire format: the magic byte, the schema
+/// id as a big-endian `u32`, then the encoded record
+pub fn frame_confluent(schema_id: u32, payload: &[u8]) -> Vec<u8> {
+    let mut framed = Vec::with_capacity(payload.len() + 5);
+    framed.push(CONFLUENT_MAGIC_BYTE);
+    framed.extend_from_slice(&schema_id.to_be_bytes());
+    framed.extend_from_slice(payload);
+    framed
+}
+
+/// Split a Confluent wire-format message into its schema id and payload
+pub fn parse_confluent_frame(message: &[u8]) -> Result<(u32, &[u8])> {
+    match message {
+        [CONFLUENT_MAGIC_BYTE, a, b, c, d, payload @ ..] => {
+            Ok((u32::from_be_bytes([*a, *b, *c, *d]), payload))
+        }
+        _ => Err(RipelError::ProcessingError(
+            "Message is not in Confluent wire format".to_string(),
+        )),
+    }
+}
+
+#[cfg(feature = "schema-registry")]
+pub use avro::AvroSerializer;
+
+#[cfg(feature = "schema-registry")]
+mod avro {
+    use super::*;
+    use crate::SchemaRegistryConfig;
+    use apache_avro::types::Value;
+    use apache_avro::Schema;
+    use std::collections::HashMap;
+    use tokio::sync::RwLock;
+    use tracing::info;
+
+    /// Avro schema for [`RipelEvent`]; `data` is carried as JSON text since
+    /// its shape differs per event type
+    const EVENT_SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "RipelEvent",
+        "namespace": "ripel",
+        "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "event_type", "type": "string"},
+            {"name": "schema_version", "type": "long", "default": 1},
+            {"name": "source", "type": "string"},
+            {"name": "timestamp", "type": {"type": "long", "logicalType": "timestamp-millis"}},
+            {"name": "data", "type": "string"},
+            {"name": "metadata", "type": {"type": "map", "values": "string"}},
+            {"name": "correlation_id", "type": "string"},
+            {"name": "partition_key", "type": ["null", "string"], "default": null}
+        ]
+    }"#;
+
+    const RECORD_NAME: &str = "ripel.RipelEvent";
+
+    /// Serializes events as Avro in the Confluent wire format, registering
+    /// the event schema with a Schema Registry on first use per subject
+    pub struct AvroSerializer {
+        config: SchemaRegistryConfig,
+        client: reqwest::Client,
+        schema: Schema,
+        schema_ids: RwLock<HashMap<String, u32>>,
+    }
+
+    impl AvroSerializer {
+        /// Create a serializer for the registry described by `config`
+        pub fn new(config: SchemaRegistryConfig) -> Result<Self> {
+            let schema = Schema::parse_str(EVENT_SCHEMA)
+                .map_err(|e| RipelError::ConfigError(format!("Invalid event schema: {}", e)))?;
+
+            Ok(Self {
+                config,
+                client: reqwest::Client::new(),
+                schema,
+                schema_ids: RwLock::new(HashMap::new()),
+            })
+        }
+
+        /// Registry subject for `topic` according to the configured strategy
+        fn subject(&self, topic: &str) -> Result<String> {
+            match self.config.schema_subject_strategy.as_str() {
+                "TopicNameStrategy" => Ok(format!("{}-value", topic)),
+                "RecordNameStrategy" => Ok(RECORD_NAME.to_string()),
+                "TopicRecordNameStrategy" => Ok(format!("{}-{}", topic, RECORD_NAME)),
+                other => Err(RipelError::ConfigError(format!(
+                    "Unknown schema subject strategy: {}",
+                    other
+                ))),
+            }
+        }
+
+        /// Look up the schema id for `subject`, registering the schema when
+        /// the registry does not know it yet.
+        ///
+        /// The cache lock is not held during registration, so concurrent first
+        /// uses of a subject may each register it; the registry returns the
+        /// same id for an identical schema.
+        async fn schema_id(&self, subject: &str) -> Result<u32> {
+            if let Some(id) = self.schema_ids.read().await.get(subject) {
+                return Ok(*id);
+            }
+
+            let id = self.register(subject).await?;
+            info!(subject = %subject, schema_id = id, "Registered event schema");
+            self.schema_ids.write().await.insert(subject.to_string(), id);
+            Ok(id)
+        }
+
+        /// Register the event schema under `subject` and return its id
+        async fn register(&self, subject: &str) -> Result<u32> {
+            let url = format!(
+                "{}/subjects/{}/versions",
+                self.config.url.trim_end_matches('/'),
+                subject
+            );
+            let mut request = self
+                .client
+                .post(&url)
+                .header("Content-Type", "application/vnd.schemaregistry.v1+json")
+                .json(&serde_json::json!({ "schema": self.schema.canonical_form() }));
+            if let Some(username) = &self.config.username {
+                request = request.basic_auth(username, self.config.password.as_ref());
+            }
+
+            let response = request
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(|e| match e.status() {
+                    // Rejected schemas and bad credentials fail the same way on retry
+                    Some(status) if status.is_client_error() => RipelError::ConfigError(format!(
+                        "Schema registry rejected subject {}: {}",
+                        subject, e
+                    )),
+                    _ => RipelError::schema_registry("Schema registry request failed", e),
+                })?;
+            let body: serde_json::Value = response
+                .json()
+                .await
+                .map_err(|e| RipelError::schema_registry("Invalid schema registry response", e))?;
+
+            body["id"]
+                .as_u64()
+                .and_then(|id| u32::try_from(id).ok())
+                .ok_or_else(|| {
+                    RipelError::ProcessingError(format!("Schema registry returned no id: {}", body))
+                })
+        }
+
+        fn to_avro(&self, event: &RipelEvent) -> Result<Vec<u8>> {
+            let partition_key = match &event.partition_key {
+                Some(key) => Value::Union(1, Box::new(Value::String(key.clone()))),
+                None => Value::Union(0, Box::new(Value::Null)),
+            };
+
+            let record = Value::Record(vec![
+                ("id".to_string(), Value::String(event.id.clone())),
+                ("event_type".to_string(), Value::String(event.event_type.clone())),
+                (
+                    "schema_version".to_string(),
+                    Value::Long(i64::from(event.schema_version)),
+                ),
+                ("source".to_string(), Value::String(event.source.clone())),
+                (
+                    "timestamp".to_string(),
+                    Value::TimestampMillis(event.timestamp.timestamp_millis()),
+                ),
+                ("data".to_string(), Value::String(event.data.to_string())),
+                (
+                    "metadata".to_string(),
+                    Value::Map(
+                        event
+                            .metadata
+                            .iter()
+                            .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+                            .collect(),
+                    ),
+                ),
+                ("correlation_id".to_string(), Value::String(event.correlation_id.clone())),
+                ("partition_key".to_string(), partition_key),
+            ]);
+
+            apache_avro::to_avro_datum(&self.schema, record)
+                .map_err(|e| RipelError::ProcessingError(format!("Avro encoding failed: {}", e)))
+        }
+    }
+
+    #[async_trait]
+    impl Serializer for AvroSerializer {
+        async fn serialize(&self, topic: &str, event: &RipelEvent) -> Result<Vec<u8>> {
+            let schema_id = self.schema_id(&self.subject(topic)?).await?;
+            Ok(frame_confluent(schema_id, &self.to_avro(event)?))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use apache_avro::from_avro_datum;
+        use serde_json::json;
+
+        #[test]
+        fn test_subject_strategies() {
+            let mut config = SchemaRegistryConfig::default();
+            assert_eq!(
+                AvroSerializer::new(config.clone()).unwrap().subject("orders").unwrap(),
+                "orders-value"
+            );
+
+            config.schema_subject_strategy = "TopicRecordNameStrategy".to_string();
+            assert_eq!(
+                AvroSerializer::new(config.clone()).unwrap().subject("orders").unwrap(),
+                "orders-ripel.RipelEvent"
+            );
+
+            config.schema_subject_strategy = "Unknown".to_string();
+            assert!(AvroSerializer::new(config).unwrap().subject("orders").is_err());
+        }
+
+        #[tokio::test]
+        async fn test_unreachable_registry_is_transient() {
+            let config = SchemaRegistryConfig {
+                url: "http://127.0.0.1:1".to_string(),
+                ..Default::default()
+            };
+            let serializer = AvroSerializer::new(config).unwrap();
+
+            let error = serializer.schema_id("orders-value").await.unwrap_err();
+            assert!(matches!(error, RipelError::SchemaRegistryError { .. }));
+            assert!(error.is_transient());
+        }
+
+        #[test]
+        fn test_avro_encoding_round_trip() {
+            let serializer = AvroSerializer::new(SchemaRegistryConfig::default()).unwrap();
+            let event = RipelEvent::new("user.created", "user-service", json!({"id": 1}))
+                .with_metadata("tenant", "acme");
+
+            let encoded = serializer.to_avro(&event).unwrap();
+            let decoded = from_avro_datum(&serializer.schema, &mut encoded.as_slice(), None).unwrap();
+
+            let Value::Record(fields) = decoded else {
+                panic!("expected a record");
+            };
+            assert_eq!(fields[0], ("id".to_string(), Value::String(event.id.clone())));
+            assert_eq!(fields[2], ("schema_version".to_string(), Value::Long(1)));
+            assert_eq!(fields[5], ("data".to_string(), Value::String(r#"{"id":1}"#.to_string())));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_confluent_framing() {
+        let framed = frame_confluent(42, b"payload");
+
+        assert_eq!(&framed[..5], &[0, 0, 0, 0, 42]);
+        assert_eq!(&framed[5..], b"payload");
+        assert_eq!(parse_confluent_frame(&framed).unwrap(), (42, &b"payload"[..]));
+
+        assert!(parse_confluent_frame(&[1, 0, 0, 0, 42]).is_err());
+        assert!(parse_confluent_frame(&[0, 0, 0]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_json_serializer() {
+        let event = RipelEvent::new("user.created", "user-service", json!({"id": 1}));
+        let bytes = JsonSerializer.serialize("users", &event).await.unwrap();
+
+        let decoded: RipelEvent = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(decoded, event);
+    }
+}
@@ -1,6 +1,8 @@
 //! Configuration for MySQL CDC
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 
 /// Table-specific CDC configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,6 +53,17 @@ impl TableConfig {
         self.capture_before = false;
         self
     }
+
+    /// Keep only the captured columns of `row`: the included ones (all when
+    /// none are listed) minus the excluded ones
+    pub fn project_columns(&self, row: HashMap<String, Value>) -> HashMap<String, Value> {
+        row.into_iter()
+            .filter(|(column, _)| {
+                (self.include_columns.is_empty() || self.include_columns.contains(column))
+                    && !self.exclude_columns.contains(column)
+            })
+            .collect()
+    }
 }
 
 /// CDC filter configuration
@@ -143,17 +156,32 @@ impl FilterConfig {
         self.operations.contains(&operation.to_lowercase())
     }
 
-    /// Simple pattern matching (supports * wildcard)
+    /// Glob-style pattern matching where each `*` matches any run of
+    /// characters, including none
     fn matches_pattern(&self, text: &str, pattern: &str) -> bool {
-        if pattern.contains('*') {
-            // Simple wildcard matching
-            let parts: Vec<&str> = pattern.split('*').collect();
-            if parts.len() == 2 {
-                let (prefix, suffix) = (parts[0], parts[1]);
-                return text.starts_with(prefix) && text.ends_with(suffix);
+        let mut segments = pattern.split('*');
+
+        // Text before the first wildcard must be a prefix
+        let Some(mut rest) = segments.next().and_then(|prefix| text.strip_prefix(prefix)) else {
+            return false;
+        };
+
+        let segments: Vec<&str> = segments.collect();
+        let Some((suffix, middle)) = segments.split_last() else {
+            // No wildcard
+            return rest.is_empty();
+        };
+
+        // Taking the leftmost match of each middle segment leaves the most
+        // room for the remaining ones
+        for segment in middle {
+            match rest.find(segment) {
+                Some(index) => rest = &rest[index + segment.len()..],
+                None => return false,
             }
         }
-        false
+
+        rest.ends_with(suffix)
     }
 }
 
@@ -177,6 +205,29 @@ mod tests {
         assert!(!config.capture_before);
     }
 
+    #[test]
+    fn test_project_columns() {
+        let row: HashMap<String, Value> = [
+            ("id", Value::from(1)),
+            ("email", Value::from("a@example.com")),
+            ("password", Value::from("hash")),
+        ]
+        .into_iter()
+        .map(|(column, value)| (column.to_string(), value))
+        .collect();
+
+        let excluded = TableConfig::new("users").exclude_column("password").project_columns(row.clone());
+        assert_eq!(excluded.len(), 2);
+        assert!(!excluded.contains_key("password"));
+
+        let included = TableConfig::new("users")
+            .include_column("id")
+            .include_column("password")
+            .exclude_column("password")
+            .project_columns(row);
+        assert_eq!(included.keys().collect::<Vec<_>>(), vec!["id"]);
+    }
+
     #[test]
     fn test_filter_config() {
         let filter = FilterConfig::default();
@@ -199,4 +250,36 @@ mod tests {
         assert!(!filter.should_include_table("temp_logs"));
         assert!(filter.should_include_table("users"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_multiple_wildcards() {
+        let filter = FilterConfig::default();
+
+        assert!(filter.matches_pattern("my_foo_table", "*foo*"));
+        assert!(filter.matches_pattern("foo", "*foo*"));
+        assert!(!filter.matches_pattern("my_table", "*foo*"));
+
+        assert!(filter.matches_pattern("abc", "a*b*c"));
+        assert!(filter.matches_pattern("a_x_b_y_c", "a*b*c"));
+        assert!(!filter.matches_pattern("a_x_c_y_b", "a*b*c"));
+        assert!(!filter.matches_pattern("ab", "a*b*c"));
+
+        assert!(filter.matches_pattern("log_2024_archive", "log_*_archive"));
+        assert!(!filter.matches_pattern("log_2024_archive_old", "log_*_archive"));
+
+        // Leading and trailing wildcards
+        assert!(filter.matches_pattern("orders_archive", "*_archive"));
+        assert!(!filter.matches_pattern("archive_orders", "*_archive"));
+        assert!(filter.matches_pattern("temp_data", "temp_*"));
+        assert!(!filter.matches_pattern("data_temp", "temp_*"));
+        assert!(filter.matches_pattern("anything", "*"));
+
+        // Segments may not overlap
+        assert!(!filter.matches_pattern("aba", "ab*ba"));
+        assert!(filter.matches_pattern("abba", "ab*ba"));
+
+        // No wildcard means an exact match
+        assert!(filter.matches_pattern("users", "users"));
+        assert!(!filter.matches_pattern("users_old", "users"));
+    }
+}
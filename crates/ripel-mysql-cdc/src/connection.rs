@@ -1,7 +1,9 @@
 //! Database connection management
 
 use ripel_core::{Result, RipelError};
-use sqlx::{ConnectOptions, MySql, Pool};
+use ripel_shared::AsyncConnectionHealthCheck;
+use sqlx::mysql::MySqlPoolOptions;
+use sqlx::{ConnectOptions, MySql, Pool, Row};
 use std::time::Duration;
 use tracing::{info, instrument};
 
@@ -16,19 +18,18 @@ impl MySqlConnectionManager {
     pub async fn new(connection_url: &str, max_connections: u32) -> Result<Self> {
         info!("Creating MySQL connection pool");
         
-        let pool = sqlx::MySqlPool::connect_with(
-            sqlx::mysql::MySqlConnectOptions::from_url(
-                &connection_url.parse()
-                    .map_err(|e| RipelError::DatabaseError(format!("Invalid URL: {}", e)))?
-            )
-            .map_err(|e| RipelError::DatabaseError(format!("Invalid connection options: {}", e)))?
+        let options = sqlx::mysql::MySqlConnectOptions::from_url(
+            &connection_url.parse()
+                .map_err(|e| RipelError::database("Invalid URL", e))?
         )
-        .max_connections(max_connections)
-        .acquire_timeout(Duration::from_secs(30))
-        .build();
+        .map_err(|e| RipelError::database("Invalid connection options", e))?;
 
-        let pool = pool.await
-            .map_err(|e| RipelError::DatabaseError(format!("Failed to create pool: {}", e)))?;
+        let pool = MySqlPoolOptions::new()
+            .max_connections(max_connections)
+            .acquire_timeout(Duration::from_secs(30))
+            .connect_with(options)
+            .await
+            .map_err(|e| RipelError::database("Failed to create pool", e))?;
 
         Ok(Self { pool })
     }
@@ -43,17 +44,22 @@ impl MySqlConnectionManager {
         sqlx::query("SELECT 1")
             .fetch_one(&self.pool)
             .await
-            .map_err(|e| RipelError::DatabaseError(format!("Connection test failed: {}", e)))?;
+            .map_err(|e| RipelError::database("Connection test failed", e))?;
         
         Ok(())
     }
 
+    /// Health check that runs the connection test against this pool
+    pub fn health_check(&self, name: impl Into<String>) -> AsyncConnectionHealthCheck {
+        pool_health_check(name, self.pool.clone())
+    }
+
     /// Get database version
     pub async fn get_version(&self) -> Result<String> {
         let row = sqlx::query("SELECT VERSION() as version")
             .fetch_one(&self.pool)
             .await
-            .map_err(|e| RipelError::DatabaseError(format!("Failed to get version: {}", e)))?;
+            .map_err(|e| RipelError::database("Failed to get version", e))?;
         
         let version: String = row.get("version");
         Ok(version)
@@ -64,7 +70,7 @@ impl MySqlConnectionManager {
         let row = sqlx::query("SHOW VARIABLES LIKE 'log_bin'")
             .fetch_optional(&self.pool)
             .await
-            .map_err(|e| RipelError::DatabaseError(format!("Failed to check binlog status: {}", e)))?;
+            .map_err(|e| RipelError::database("Failed to check binlog status", e))?;
 
         if let Some(row) = row {
             let value: String = row.get("Value");
@@ -79,13 +85,21 @@ impl MySqlConnectionManager {
         let row = sqlx::query("SHOW VARIABLES LIKE 'binlog_format'")
             .fetch_one(&self.pool)
             .await
-            .map_err(|e| RipelError::DatabaseError(format!("Failed to get binlog format: {}", e)))?;
+            .map_err(|e| RipelError::database("Failed to get binlog format", e))?;
 
         let format: String = row.get("Value");
         Ok(format)
     }
 }
 
+/// Health check that runs `SELECT 1` against `pool`
+pub fn pool_health_check(name: impl Into<String>, pool: Pool<MySql>) -> AsyncConnectionHealthCheck {
+    AsyncConnectionHealthCheck::new(name, move || {
+        let pool = pool.clone();
+        async move { sqlx::query("SELECT 1").fetch_one(&pool).await.is_ok() }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
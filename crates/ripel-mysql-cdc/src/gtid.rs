@@ -0,0 +1,152 @@
+//! GTID sets for GTID-based binlog positioning
+
+use mysql_async::Sid;
+use ripel_core::{Result, RipelError};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Set of GTIDs in MySQL's text form, e.g.
+/// `3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5:7`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GtidSet {
+    /// Inclusive transaction number ranges per source server UUID
+    intervals: BTreeMap<String, Vec<(u64, u64)>>,
+}
+
+impl GtidSet {
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Add transaction `gno` of server `uuid`, merging adjacent ranges
+    pub fn add(&mut self, uuid: &str, gno: u64) {
+        let ranges = self.intervals.entry(uuid.to_lowercase()).or_default();
+        ranges.push((gno, gno));
+        normalize(ranges);
+    }
+
+    /// Whether transaction `gno` of server `uuid` is in the set
+    pub fn contains(&self, uuid: &str, gno: u64) -> bool {
+        self.intervals
+            .get(&uuid.to_lowercase())
+            .is_some_and(|ranges| ranges.iter().any(|(start, end)| (*start..=*end).contains(&gno)))
+    }
+
+    /// The set in the form expected by `COM_BINLOG_DUMP_GTID`
+    pub fn sids(&self) -> Result<Vec<Sid<'static>>> {
+        self.intervals
+            .iter()
+            .map(|(uuid, ranges)| {
+                Sid::from_str(&format_sid(uuid, ranges))
+                    .map_err(|e| RipelError::ConfigError(format!("Invalid GTID set: {}", e)))
+            })
+            .collect()
+    }
+}
+
+impl FromStr for GtidSet {
+    type Err = RipelError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = |part: &str| RipelError::ConfigError(format!("Invalid GTID set entry: {}", part));
+        let mut set = GtidSet::default();
+
+        // Servers print long sets with a newline after each comma
+        for part in s.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+            let mut fields = part.split(':');
+            let uuid = fields.next().filter(|uuid| uuid.len() == 36).ok_or_else(|| invalid(part))?;
+
+            let ranges = set.intervals.entry(uuid.to_lowercase()).or_default();
+            for range in fields {
+                let (start, end) = range.split_once('-').unwrap_or((range, range));
+                let start: u64 = start.parse().map_err(|_| invalid(part))?;
+                let end: u64 = end.parse().map_err(|_| invalid(part))?;
+                if start == 0 || start > end {
+                    return Err(invalid(part));
+                }
+                ranges.push((start, end));
+            }
+            if ranges.is_empty() {
+                return Err(invalid(part));
+            }
+            normalize(ranges);
+        }
+
+        Ok(set)
+    }
+}
+
+impl fmt::Display for GtidSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sids: Vec<_> = self
+            .intervals
+            .iter()
+            .map(|(uuid, ranges)| format_sid(uuid, ranges))
+            .collect();
+        f.write_str(&sids.join(","))
+    }
+}
+
+fn format_sid(uuid: &str, ranges: &[(u64, u64)]) -> String {
+    let mut sid = uuid.to_string();
+    for (start, end) in ranges {
+        if start == end {
+            sid.push_str(&format!(":{}", start));
+        } else {
+            sid.push_str(&format!(":{}-{}", start, end));
+        }
+    }
+    sid
+}
+
+/// Sort `ranges` and merge overlapping or adjacent ones
+fn normalize(ranges: &mut Vec<(u64, u64)>) {
+    ranges.sort_unstable();
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges.drain(..) {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    *ranges = merged;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UUID_A: &str = "3E11FA47-71CA-11E1-9E33-C80AA9429562";
+    const UUID_B: &str = "a4f2b3c1-0000-11e1-9e33-c80aa9429562";
+
+    #[test]
+    fn test_parse_and_format() {
+        let set: GtidSet = format!("{}:1-5:7,\n{}:3", UUID_A, UUID_B).parse().unwrap();
+
+        assert_eq!(
+            set.to_string(),
+            "3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5:7,a4f2b3c1-0000-11e1-9e33-c80aa9429562:3"
+        );
+        assert!(set.contains(UUID_A, 4));
+        assert!(!set.contains(UUID_A, 6));
+        assert_eq!(set.sids().unwrap().len(), 2);
+
+        assert!("".parse::<GtidSet>().unwrap().is_empty());
+        assert!("not-a-uuid:1-5".parse::<GtidSet>().is_err());
+        assert!(format!("{}:5-1", UUID_A).parse::<GtidSet>().is_err());
+    }
+
+    #[test]
+    fn test_add_merges_ranges() {
+        let mut set: GtidSet = format!("{}:1-5:7", UUID_A).parse().unwrap();
+
+        set.add(UUID_A, 6);
+        set.add(UUID_B, 1);
+
+        assert_eq!(
+            set.to_string(),
+            "3e11fa47-71ca-11e1-9e33-c80aa9429562:1-7,a4f2b3c1-0000-11e1-9e33-c80aa9429562:1"
+        );
+    }
+}
@@ -1,22 +1,26 @@
 //! MySQL Change Data Capture for RIPeL
 
-use ripel_core::{DatabaseChangeEvent, OperationType, RipelEvent, Result, RipelError};
-use ripel_shared::{EventMetrics, PerfTimer};
+use ripel_core::{DatabaseChangeEvent, OperationType, Result, RipelError};
+use ripel_shared::EventMetrics;
 use async_trait::async_trait;
 use serde_json::{json, Value};
-use sqlx::{MySql, Pool, Row};
+use sqlx::{MySql, Pool};
 use std::collections::HashMap;
-use tokio_stream::StreamExt;
-use tracing::{error, info, instrument, warn};
+use std::sync::Arc;
+use tracing::{info, instrument};
 use uuid::Uuid;
 
 pub mod binlog;
 pub mod connection;
+pub mod gtid;
 pub mod config;
+pub mod position;
 
 pub use binlog::*;
 pub use connection::*;
+pub use gtid::*;
 pub use config::*;
+pub use position::*;
 
 /// MySQL CDC configuration
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
@@ -38,6 +42,12 @@ pub struct MySqlCdcConfig {
     
     /// Binlog position to start from
     pub binlog_position: Option<u32>,
+
+    /// GTID set already processed; when set, replication is positioned by
+    /// GTID instead of `binlog_filename`/`binlog_position`. Prefer it on
+    /// servers with `gtid_mode=ON`, since it survives failover to a replica.
+    #[serde(default)]
+    pub gtid_set: Option<String>,
     
     /// Maximum events per batch
     pub batch_size: usize,
@@ -52,6 +62,7 @@ impl Default for MySqlCdcConfig {
             server_id: 1001,
             binlog_filename: None,
             binlog_position: None,
+            gtid_set: None,
             batch_size: 1000,
         }
     }
@@ -61,6 +72,9 @@ impl Default for MySqlCdcConfig {
 pub struct MySqlCdcProcessor {
     config: MySqlCdcConfig,
     connection_pool: Pool<MySql>,
+    position_store: Option<Arc<dyn PositionStore>>,
+    filter: FilterConfig,
+    table_configs: HashMap<String, TableConfig>,
 }
 
 impl MySqlCdcProcessor {
@@ -68,16 +82,40 @@ impl MySqlCdcProcessor {
     pub async fn new(config: MySqlCdcConfig) -> Result<Self> {
         let connection_pool = sqlx::MySqlPool::connect(&config.connection_url)
             .await
-            .map_err(|e| RipelError::DatabaseError(format!("Connection failed: {}", e)))?;
+            .map_err(|e| RipelError::database("Connection failed", e))?;
 
         Ok(Self {
             config,
             connection_pool,
+            position_store: None,
+            filter: FilterConfig::default(),
+            table_configs: HashMap::new(),
         })
     }
 
+    /// Only emit changes to databases, tables and operations `filter` accepts
+    pub fn with_filter(mut self, filter: FilterConfig) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Apply `table_config` to the changes of its table
+    pub fn with_table_config(mut self, table_config: TableConfig) -> Self {
+        self.table_configs.insert(table_config.name.clone(), table_config);
+        self
+    }
+
+    /// Checkpoint the binlog position to `store` and resume from it on start
+    pub fn with_position_store(mut self, store: Arc<dyn PositionStore>) -> Self {
+        self.position_store = Some(store);
+        self
+    }
+
     /// Start processing CDC events
-    #[instrument(skip(self))]
+    ///
+    /// Streams row changes from the binlog until the server closes the
+    /// stream or `event_handler` fails.
+    #[instrument(skip(self, event_handler))]
     pub async fn start_processing<F>(&self, mut event_handler: F) -> Result<()>
     where
         F: FnMut(DatabaseChangeEvent) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send>> + Send,
@@ -88,76 +126,68 @@ impl MySqlCdcProcessor {
             "Starting MySQL CDC processing"
         );
 
-        // In a real implementation, this would connect to MySQL binlog
-        // For now, we'll simulate CDC events by polling for changes
-        self.poll_for_changes(event_handler).await
-    }
-
-    /// Poll database for changes (simplified CDC simulation)
-    async fn poll_for_changes<F>(&self, mut event_handler: F) -> Result<()>
-    where
-        F: FnMut(DatabaseChangeEvent) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send>> + Send,
-    {
-        let tables = if self.config.tables.is_empty() {
-            self.get_all_tables().await?
-        } else {
-            self.config.tables.clone()
-        };
-
-        info!("Monitoring tables: {:?}", tables);
-
-        // This is a simplified implementation
-        // In a real CDC system, you would:
-        // 1. Connect to MySQL binlog using mysql_cdc or similar
-        // 2. Parse binlog events
-        // 3. Filter by database and tables
-        // 4. Convert to DatabaseChangeEvent format
-
-        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-        info!("CDC processing would start here");
-
-        Ok(())
-    }
-
-    /// Get all tables in the database
-    async fn get_all_tables(&self) -> Result<Vec<String>> {
-        let _timer = PerfTimer::new("mysql_cdc_get_tables_duration")
-            .with_label("database", &self.config.database);
+        info!(
+            tables = ?self.config.tables,
+            filter = ?self.filter,
+            "Monitoring tables"
+        );
 
-        let query = "SELECT table_name FROM information_schema.tables WHERE table_schema = ?";
-        let rows = sqlx::query(query)
-            .bind(&self.config.database)
-            .fetch_all(&self.connection_pool)
+        let mut reader = BinlogReader::new(self.config.clone());
+        if let Some(store) = &self.position_store {
+            reader = reader.with_position_store(store.clone());
+        }
+        reader
+            .start_reading(|change| {
+                if !self.should_emit(&change) {
+                    return Box::pin(async { Ok(()) });
+                }
+                let event = self.create_change_event(change.operation, &change.table, change.before, change.after);
+                event_handler(event)
+            })
             .await
-            .map_err(|e| RipelError::DatabaseError(format!("Failed to get tables: {}", e)))?;
-
-        let tables: Vec<String> = rows
-            .into_iter()
-            .map(|row| row.get::<String, _>("table_name"))
-            .collect();
+    }
 
-        Ok(tables)
+    /// Whether the filter accepts the database, table and operation of `change`
+    fn should_emit(&self, change: &RowChange) -> bool {
+        self.filter.should_include_database(&change.database)
+            && self.filter.should_include_table(&change.table)
+            && self.filter.should_capture_operation(change.operation.as_str())
     }
 
-    /// Create a database change event from raw data
+    /// Create a database change event from raw data, applying the table's
+    /// column selection, before-image capture and event type override
     fn create_change_event(
         &self,
         operation: OperationType,
         table: &str,
-        before: Option<HashMap<String, Value>>,
-        after: Option<HashMap<String, Value>>,
+        mut before: Option<HashMap<String, Value>>,
+        mut after: Option<HashMap<String, Value>>,
     ) -> DatabaseChangeEvent {
+        let table_config = self.table_configs.get(table);
+        if let Some(table_config) = table_config {
+            if !table_config.capture_before {
+                before = None;
+            }
+            before = before.map(|data| table_config.project_columns(data));
+            after = after.map(|data| table_config.project_columns(data));
+        }
+
         let before_json = before.map(|data| json!(data));
         let after_json = after.map(|data| json!(data));
 
-        DatabaseChangeEvent::new(
+        let mut event = DatabaseChangeEvent::new(
             operation,
             &self.config.database,
             table,
             before_json,
             after_json,
         )
-        .with_transaction_id(Uuid::new_v4().to_string())
+        .with_transaction_id(Uuid::new_v4().to_string());
+
+        if let Some(event_type) = table_config.and_then(|config| config.event_type_override.as_ref()) {
+            event.base_event.event_type = event_type.clone();
+        }
+        event
     }
 
     /// Health check for the CDC processor
@@ -166,7 +196,7 @@ impl MySqlCdcProcessor {
         let _result = sqlx::query("SELECT 1")
             .fetch_one(&self.connection_pool)
             .await
-            .map_err(|e| RipelError::DatabaseError(format!("Health check failed: {}", e)))?;
+            .map_err(|e| RipelError::database("Health check failed", e))?;
 
         Ok(())
     }
@@ -185,22 +215,22 @@ pub struct LoggingCdcProcessor;
 
 #[async_trait]
 impl MySqlCdcEventProcessor for LoggingCdcProcessor {
-    #[instrument(skip(self, data))]
-    async fn process_insert(&self, table: &str, data: HashMap<String, Value>) -> Result<()> {
+    #[instrument(skip(self, _data))]
+    async fn process_insert(&self, table: &str, _data: HashMap<String, Value>) -> Result<()> {
         info!(table = table, "INSERT event");
         EventMetrics::database_operation("insert", table, tokio::time::Duration::from_millis(1));
         Ok(())
     }
 
-    #[instrument(skip(self, before, after))]
-    async fn process_update(&self, table: &str, before: HashMap<String, Value>, after: HashMap<String, Value>) -> Result<()> {
+    #[instrument(skip(self, _before, _after))]
+    async fn process_update(&self, table: &str, _before: HashMap<String, Value>, _after: HashMap<String, Value>) -> Result<()> {
         info!(table = table, "UPDATE event");
         EventMetrics::database_operation("update", table, tokio::time::Duration::from_millis(1));
         Ok(())
     }
 
-    #[instrument(skip(self, data))]
-    async fn process_delete(&self, table: &str, data: HashMap<String, Value>) -> Result<()> {
+    #[instrument(skip(self, _data))]
+    async fn process_delete(&self, table: &str, _data: HashMap<String, Value>) -> Result<()> {
         info!(table = table, "DELETE event");
         EventMetrics::database_operation("delete", table, tokio::time::Duration::from_millis(1));
         Ok(())
@@ -219,14 +249,21 @@ mod tests {
         assert_eq!(config.batch_size, 1000);
     }
 
-    #[test]
-    fn test_create_change_event() {
+    fn test_processor() -> MySqlCdcProcessor {
         let config = MySqlCdcConfig::default();
         let pool = Pool::<MySql>::connect_lazy(&config.connection_url).unwrap();
-        let processor = MySqlCdcProcessor {
-            config: config.clone(),
+        MySqlCdcProcessor {
+            config,
             connection_pool: pool,
-        };
+            position_store: None,
+            filter: FilterConfig::default(),
+            table_configs: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_change_event() {
+        let processor = test_processor();
 
         let mut after = HashMap::new();
         after.insert("id".to_string(), json!(1));
@@ -245,4 +282,58 @@ mod tests {
         assert!(event.after.is_some());
         assert!(event.before.is_none());
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_table_config_shapes_events() {
+        let processor = test_processor().with_table_config(
+            TableConfig::new("users")
+                .exclude_column("password")
+                .with_event_type("user.changed")
+                .without_before_capture(),
+        );
+
+        let row = HashMap::from([
+            ("id".to_string(), json!(1)),
+            ("password".to_string(), json!("hash")),
+        ]);
+        let event = processor.create_change_event(
+            OperationType::Update,
+            "users",
+            Some(row.clone()),
+            Some(row.clone()),
+        );
+
+        assert_eq!(event.after, Some(json!({"id": 1})));
+        assert!(event.before.is_none());
+        assert_eq!(event.base_event.event_type, "user.changed");
+
+        // Tables without a config keep every column and the default type
+        let event = processor.create_change_event(OperationType::Update, "orders", Some(row.clone()), Some(row));
+        assert_eq!(event.after, Some(json!({"id": 1, "password": "hash"})));
+        assert!(event.before.is_some());
+        assert_eq!(event.base_event.event_type, "database.ripel.orders.update");
+    }
+
+    #[tokio::test]
+    async fn test_filter_gates_changes() {
+        let mut filter = FilterConfig {
+            operations: vec!["insert".to_string()],
+            ..Default::default()
+        };
+        filter.exclude_tables.push("audit_*".to_string());
+        let processor = test_processor().with_filter(filter);
+
+        let change = |operation, database: &str, table: &str| RowChange {
+            operation,
+            database: database.to_string(),
+            table: table.to_string(),
+            before: None,
+            after: None,
+        };
+
+        assert!(processor.should_emit(&change(OperationType::Insert, "ripel", "users")));
+        assert!(!processor.should_emit(&change(OperationType::Delete, "ripel", "users")));
+        assert!(!processor.should_emit(&change(OperationType::Insert, "ripel", "audit_log")));
+        assert!(!processor.should_emit(&change(OperationType::Insert, "mysql", "users")));
+    }
+}
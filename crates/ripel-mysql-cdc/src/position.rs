@@ -0,0 +1,87 @@
+//! Binlog position checkpointing
+
+use crate::BinlogCoordinate;
+use async_trait::async_trait;
+use ripel_core::{Result, RipelError};
+use std::path::PathBuf;
+
+/// Durable storage for the last processed binlog coordinate: a file
+/// position, or the executed GTID set in GTID mode
+#[async_trait]
+pub trait PositionStore: Send + Sync {
+    /// Load the last saved coordinate, if any
+    async fn load(&self) -> Result<Option<BinlogCoordinate>>;
+
+    /// Save `coordinate` as the point to resume from
+    async fn save(&self, coordinate: BinlogCoordinate) -> Result<()>;
+}
+
+/// Position store keeping the coordinate as JSON in a file
+pub struct FilePositionStore {
+    path: PathBuf,
+}
+
+impl FilePositionStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl PositionStore for FilePositionStore {
+    async fn load(&self) -> Result<Option<BinlogCoordinate>> {
+        match tokio::fs::read(&self.path).await {
+            Ok(contents) => Ok(Some(serde_json::from_slice(&contents)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(RipelError::InternalError(format!(
+                "Failed to read position file {}: {}",
+                self.path.display(),
+                e
+            ))),
+        }
+    }
+
+    async fn save(&self, coordinate: BinlogCoordinate) -> Result<()> {
+        let contents = serde_json::to_vec(&coordinate)?;
+
+        // Write then rename so a crash never leaves a truncated checkpoint
+        let tmp_path = self.path.with_extension("tmp");
+        let written = match tokio::fs::write(&tmp_path, contents).await {
+            Ok(()) => tokio::fs::rename(&tmp_path, &self.path).await,
+            Err(e) => Err(e),
+        };
+
+        written.map_err(|e| {
+            RipelError::InternalError(format!(
+                "Failed to write position file {}: {}",
+                self.path.display(),
+                e
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BinlogPosition;
+
+    #[tokio::test]
+    async fn test_file_position_store_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FilePositionStore::new(dir.path().join("position.json"));
+
+        assert!(store.load().await.unwrap().is_none());
+
+        store
+            .save(BinlogCoordinate::File(BinlogPosition::new("mysql-bin.000003", 1024)))
+            .await
+            .unwrap();
+        let gtid = BinlogCoordinate::Gtid {
+            gtid_set: "3e11fa47-71ca-11e1-9e33-c80aa9429562:1-42".to_string(),
+        };
+        store.save(gtid.clone()).await.unwrap();
+
+        assert_eq!(store.load().await.unwrap(), Some(gtid));
+    }
+}
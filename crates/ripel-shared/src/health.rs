@@ -1,8 +1,20 @@
 //! Health check utilities
 
-use crate::observability::{HealthCheck, HealthStatus};
+use crate::observability::{
+    read_request_line, write_response, AsyncHealthCheck, HealthAggregator, HealthCheck,
+    HealthStatus, ACCEPT_ERROR_BACKOFF,
+};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
 
 /// Simple health check that always returns healthy
 pub struct AlwaysHealthy {
@@ -48,13 +60,14 @@ impl ActivityBasedHealthCheck {
     }
 }
 
-impl HealthCheck for ActivityBasedHealthCheck {
+#[async_trait]
+impl AsyncHealthCheck for ActivityBasedHealthCheck {
     fn name(&self) -> &str {
         &self.name
     }
 
-    fn check(&self) -> HealthStatus {
-        let last_activity = *self.last_activity.blocking_read();
+    async fn check(&self) -> HealthStatus {
+        let last_activity = *self.last_activity.read().await;
         let elapsed = last_activity.elapsed();
         
         if elapsed > self.timeout {
@@ -95,19 +108,165 @@ impl HealthCheck for ConnectionHealthCheck {
     }
 
     fn check(&self) -> HealthStatus {
-        if (self.check_fn)() {
-            HealthStatus::Healthy
-        } else {
-            HealthStatus::Unhealthy {
-                reason: "Connection check failed".to_string(),
+        connection_status((self.check_fn)())
+    }
+}
+
+/// Boxed future returned by an async connection probe
+pub type ProbeFuture = Pin<Box<dyn Future<Output = bool> + Send>>;
+
+/// Connection-based health check whose probe is async, e.g. a database ping
+pub struct AsyncConnectionHealthCheck {
+    name: String,
+    probe: Box<dyn Fn() -> ProbeFuture + Send + Sync>,
+}
+
+impl AsyncConnectionHealthCheck {
+    pub fn new<F, Fut>(name: impl Into<String>, probe: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = bool> + Send + 'static,
+    {
+        Self {
+            name: name.into(),
+            probe: Box::new(move || Box::pin(probe())),
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncHealthCheck for AsyncConnectionHealthCheck {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn check(&self) -> HealthStatus {
+        connection_status((self.probe)().await)
+    }
+}
+
+fn connection_status(connected: bool) -> HealthStatus {
+    if connected {
+        HealthStatus::Healthy
+    } else {
+        HealthStatus::Unhealthy {
+            reason: "Connection check failed".to_string(),
+        }
+    }
+}
+
+/// HTTP server exposing a [`HealthAggregator`] for liveness and readiness probes.
+///
+/// `/livez` answers 200 whenever the process is serving requests, without
+/// running any checks. `/readyz` answers 200 when all checks are healthy, 503
+/// when any is unhealthy and the configured degraded status otherwise (200 by
+/// default), along with the JSON status of every component.
+pub struct HealthServer {
+    aggregator: Arc<HealthAggregator>,
+    degraded_status: u16,
+}
+
+impl HealthServer {
+    pub fn new(aggregator: Arc<HealthAggregator>) -> Self {
+        Self {
+            aggregator,
+            degraded_status: 200,
+        }
+    }
+
+    /// Set the `/readyz` status code used when a check is degraded
+    pub fn with_degraded_status(mut self, status: u16) -> Self {
+        self.degraded_status = status;
+        self
+    }
+
+    /// Serve probes on `listener` until the returned task is aborted
+    pub fn serve(self, listener: TcpListener) -> JoinHandle<()> {
+        let server = Arc::new(self);
+        tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, peer)) => {
+                        let server = server.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = server.respond(stream).await {
+                                debug!(peer = %peer, error = %e, "Health request failed");
+                            }
+                        });
+                    }
+                    Err(e) => {
+                        warn!(error = %e, "Failed to accept health connection");
+                        tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                    }
+                }
+            }
+        })
+    }
+
+    async fn respond(&self, mut stream: TcpStream) -> std::io::Result<()> {
+        let (method, path) = read_request_line(&mut stream).await?;
+        match (method.as_str(), path.as_str()) {
+            ("GET", "/livez") => {
+                return write_response(&mut stream, "200 OK", "text/plain", "OK\n").await;
+            }
+            ("GET", "/readyz") => {}
+            ("GET", _) => {
+                return write_response(&mut stream, "404 Not Found", "text/plain", "Not Found\n")
+                    .await;
+            }
+            _ => {
+                let body = "Method Not Allowed\n";
+                return write_response(&mut stream, "405 Method Not Allowed", "text/plain", body)
+                    .await;
             }
         }
+
+        let checks = self.aggregator.check_all_async().await;
+        let status = HealthAggregator::aggregate(&checks);
+        let code = match &status {
+            HealthStatus::Healthy => 200,
+            HealthStatus::Degraded { .. } => self.degraded_status,
+            HealthStatus::Unhealthy { .. } => 503,
+        };
+
+        let body = serde_json::json!({
+            "status": status,
+            "checks": checks.into_iter().collect::<BTreeMap<_, _>>(),
+        });
+        let body = serde_json::to_string(&body).unwrap_or_default();
+
+        write_response(&mut stream, &status_line(code), "application/json", &body).await
     }
 }
 
+fn status_line(code: u16) -> String {
+    let reason = match code {
+        200 => "OK",
+        204 => "No Content",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "",
+    };
+    // The space before the reason phrase is required even when it is empty
+    format!("{} {}", code, reason)
+}
+
+/// Bind `addr` and serve `/livez` and `/readyz` for `aggregator`
+pub async fn serve_health(
+    aggregator: Arc<HealthAggregator>,
+    addr: SocketAddr,
+) -> std::io::Result<JoinHandle<()>> {
+    let listener = TcpListener::bind(addr).await?;
+    info!("Health endpoint listening on {}", listener.local_addr()?);
+    Ok(HealthServer::new(aggregator).serve(listener))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
     use tokio::time::sleep;
 
     #[test]
@@ -122,29 +281,165 @@ mod tests {
         let check = ActivityBasedHealthCheck::new("test", Duration::from_millis(100));
         
         // Should be healthy initially
-        assert!(matches!(check.check(), HealthStatus::Healthy));
+        assert!(matches!(check.check().await, HealthStatus::Healthy));
         
         // Wait for timeout
         sleep(Duration::from_millis(150)).await;
         
         // Should be unhealthy after timeout
-        assert!(matches!(check.check(), HealthStatus::Unhealthy { .. }));
+        assert!(matches!(check.check().await, HealthStatus::Unhealthy { .. }));
         
         // Record activity and check again
         check.record_activity().await;
-        assert!(matches!(check.check(), HealthStatus::Healthy));
+        assert!(matches!(check.check().await, HealthStatus::Healthy));
+    }
+
+    #[tokio::test]
+    async fn test_aggregator_with_activity_check() {
+        let aggregator = HealthAggregator::new()
+            .add_check(Box::new(AlwaysHealthy::new("always")))
+            .add_async_check(Box::new(ActivityBasedHealthCheck::new(
+                "activity",
+                Duration::from_millis(50),
+            )));
+
+        assert!(matches!(aggregator.overall_status_async().await, HealthStatus::Healthy));
+
+        sleep(Duration::from_millis(80)).await;
+
+        match aggregator.overall_status_async().await {
+            HealthStatus::Unhealthy { reason } => assert!(reason.starts_with("activity: ")),
+            other => panic!("Expected unhealthy status, got {:?}", other),
+        }
     }
 
     #[test]
     fn test_connection_health_check() {
-        let mut connected = true;
-        let check = ConnectionHealthCheck::new("test", move || connected);
+        let connected = Arc::new(AtomicBool::new(true));
+        let probe = connected.clone();
+        let check = ConnectionHealthCheck::new("test", move || probe.load(Ordering::Relaxed));
         
         assert!(matches!(check.check(), HealthStatus::Healthy));
         
         // Simulate connection failure
-        connected = false;
-        // Note: This test demonstrates the concept, but the closure captures by value
-        // In real usage, you'd use Arc<AtomicBool> or similar for shared state
+        connected.store(false, Ordering::Relaxed);
+        assert!(matches!(check.check(), HealthStatus::Unhealthy { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_async_connection_health_check() {
+        let connected = Arc::new(AtomicBool::new(true));
+        let probe = connected.clone();
+        let check = AsyncConnectionHealthCheck::new("db", move || {
+            let connected = probe.clone();
+            async move {
+                tokio::task::yield_now().await;
+                connected.load(Ordering::Relaxed)
+            }
+        });
+
+        assert_eq!(check.name(), "db");
+        assert!(matches!(check.check().await, HealthStatus::Healthy));
+
+        connected.store(false, Ordering::Relaxed);
+        assert!(matches!(check.check().await, HealthStatus::Unhealthy { .. }));
+
+        connected.store(true, Ordering::Relaxed);
+        assert!(matches!(check.check().await, HealthStatus::Healthy));
+    }
+
+    struct FixedStatus(HealthStatus);
+
+    impl HealthCheck for FixedStatus {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        fn check(&self) -> HealthStatus {
+            self.0.clone()
+        }
+    }
+
+    async fn probe(addr: SocketAddr, path: &str) -> (String, serde_json::Value) {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes())
+            .await
+            .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.lines().next().unwrap().to_string();
+        (status, serde_json::from_str(body).unwrap_or_default())
+    }
+
+    async fn start(status: HealthStatus, degraded_status: u16) -> (SocketAddr, JoinHandle<()>) {
+        let aggregator = Arc::new(HealthAggregator::new().add_check(Box::new(FixedStatus(status))));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = HealthServer::new(aggregator)
+            .with_degraded_status(degraded_status)
+            .serve(listener);
+        (addr, server)
+    }
+
+    #[tokio::test]
+    async fn test_health_endpoints() {
+        let (addr, server) = start(HealthStatus::Healthy, 200).await;
+        let (status, body) = probe(addr, "/readyz").await;
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        assert_eq!(body["status"], "Healthy");
+        assert_eq!(body["checks"]["fixed"], "Healthy");
+        server.abort();
+
+        let unhealthy = HealthStatus::Unhealthy {
+            reason: "down".to_string(),
+        };
+        let (addr, server) = start(unhealthy, 200).await;
+        assert_eq!(probe(addr, "/livez").await.0, "HTTP/1.1 200 OK");
+        let (status, body) = probe(addr, "/readyz").await;
+        assert_eq!(status, "HTTP/1.1 503 Service Unavailable");
+        assert_eq!(body["checks"]["fixed"]["Unhealthy"]["reason"], "down");
+        assert_eq!(probe(addr, "/other").await.0, "HTTP/1.1 404 Not Found");
+        server.abort();
+
+        let degraded = HealthStatus::Degraded {
+            reason: "slow".to_string(),
+        };
+        let (addr, server) = start(degraded.clone(), 429).await;
+        assert_eq!(probe(addr, "/readyz").await.0, "HTTP/1.1 429 Too Many Requests");
+        server.abort();
+
+        let (addr, server) = start(degraded, 207).await;
+        assert_eq!(probe(addr, "/readyz").await.0, "HTTP/1.1 207 ");
+        server.abort();
+    }
+
+    struct HangingCheck;
+
+    #[async_trait]
+    impl AsyncHealthCheck for HangingCheck {
+        fn name(&self) -> &str {
+            "hanging"
+        }
+
+        async fn check(&self) -> HealthStatus {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_livez_skips_checks() {
+        let aggregator = Arc::new(HealthAggregator::new().add_async_check(Box::new(HangingCheck)));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = HealthServer::new(aggregator).serve(listener);
+
+        let (status, _) = tokio::time::timeout(Duration::from_secs(1), probe(addr, "/livez"))
+            .await
+            .expect("/livez should not wait for checks");
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        server.abort();
     }
 }
\ No newline at end of file
@@ -1,11 +1,20 @@
 //! Observability features including logging, metrics, and tracing
 
-use metrics::{counter, gauge, histogram};
-use metrics_exporter_prometheus::PrometheusBuilder;
+use async_trait::async_trait;
+use futures::future::join_all;
+use metrics::{counter, gauge, histogram, Label};
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
 use once_cell::sync::OnceCell;
 use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
 use tokio::time::{Duration, Instant};
-use tracing::{info, Level};
+use tracing::{debug, info, warn, Level};
+use tracing_appender::non_blocking::{NonBlocking, WorkerGuard};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::{
     fmt::format::FmtSpan, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter,
 };
@@ -19,22 +28,37 @@ static OBSERVABILITY: OnceCell<ObservabilitySystem> = OnceCell::new();
 pub struct ObservabilitySystem {
     metrics_enabled: bool,
     tracing_enabled: bool,
+    metrics_server: Option<JoinHandle<()>>,
+    /// Keeps the non-blocking file writer flushing until `shutdown` drops it;
+    /// the system lives in a static, so it is never dropped on exit
+    log_guard: Mutex<Option<WorkerGuard>>,
 }
 
+/// Log file used when file logging is enabled without a `file_path`
+const DEFAULT_LOG_FILE: &str = "ripel.log";
+
+/// Pause after a failed accept before the HTTP endpoints try again
+pub(crate) const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+/// How long the HTTP endpoints wait for a client to send its request head
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
 impl ObservabilitySystem {
     /// Initialize the observability system
     pub fn init(config: &ObservabilityConfig) -> anyhow::Result<()> {
-        let system = Self {
+        // Initialize logging, with trace export layered in when enabled
+        let log_guard = Self::init_logging(&config.logging, &config.tracing)?;
+
+        let mut system = Self {
             metrics_enabled: config.metrics.enabled,
             tracing_enabled: config.tracing.enabled,
+            metrics_server: None,
+            log_guard: Mutex::new(log_guard),
         };
 
-        // Initialize logging
-        Self::init_logging(&config.logging)?;
-
         // Initialize metrics
         if config.metrics.enabled {
-            Self::init_metrics(&config.metrics)?;
+            system.metrics_server = Some(Self::init_metrics(&config.metrics)?);
         }
 
         // Initialize tracing
@@ -50,8 +74,14 @@ impl ObservabilitySystem {
         Ok(())
     }
 
-    /// Initialize structured logging
-    fn init_logging(config: &LoggingConfig) -> anyhow::Result<()> {
+    /// Initialize structured logging.
+    ///
+    /// Returns the guard of the file writer when file logging is enabled;
+    /// buffered lines are lost if it is dropped before exit.
+    fn init_logging(
+        config: &LoggingConfig,
+        tracing: &TracingConfig,
+    ) -> anyhow::Result<Option<WorkerGuard>> {
         let level = match config.level.to_lowercase().as_str() {
             "trace" => Level::TRACE,
             "debug" => Level::DEBUG,
@@ -65,60 +95,250 @@ impl ObservabilitySystem {
             .with_default_directive(level.into())
             .from_env_lossy();
 
-        let registry = tracing_subscriber::registry().with(env_filter);
+        let registry = tracing_subscriber::registry()
+            .with(env_filter)
+            .with(Self::tracing_layer(tracing)?);
+
+        let (file_writer, guard) = match Self::file_writer(config)? {
+            Some((writer, guard)) => (Some(writer), Some(guard)),
+            None => (None, None),
+        };
 
         match config.format.to_lowercase().as_str() {
             "json" => {
                 let json_layer = tracing_subscriber::fmt::layer()
                     .json()
                     .with_span_events(FmtSpan::CLOSE);
-                registry.with(json_layer).init();
+                let file_layer = file_writer.map(|writer| {
+                    tracing_subscriber::fmt::layer()
+                        .json()
+                        .with_span_events(FmtSpan::CLOSE)
+                        .with_writer(writer)
+                });
+                registry.with(json_layer).with(file_layer).init();
             }
             _ => {
                 let pretty_layer = tracing_subscriber::fmt::layer()
                     .pretty()
                     .with_span_events(FmtSpan::CLOSE);
-                registry.with(pretty_layer).init();
+                let file_layer = file_writer.map(|writer| {
+                    tracing_subscriber::fmt::layer()
+                        .with_ansi(false)
+                        .with_span_events(FmtSpan::CLOSE)
+                        .with_writer(writer)
+                });
+                registry.with(pretty_layer).with(file_layer).init();
             }
         }
 
-        Ok(())
+        Ok(guard)
     }
 
-    /// Initialize Prometheus metrics
-    fn init_metrics(config: &MetricsConfig) -> anyhow::Result<()> {
+    /// Open the non-blocking log file writer when file logging is enabled
+    fn file_writer(config: &LoggingConfig) -> anyhow::Result<Option<(NonBlocking, WorkerGuard)>> {
+        if !config.file_enabled {
+            return Ok(None);
+        }
+
+        let path = Path::new(config.file_path.as_deref().unwrap_or(DEFAULT_LOG_FILE));
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| anyhow::anyhow!("Invalid log file path: {}", path.display()))?;
+        let directory = path.parent().unwrap_or_else(|| Path::new(""));
+
+        let appender = RollingFileAppender::builder()
+            .rotation(Rotation::NEVER)
+            .filename_prefix(file_name.to_string_lossy())
+            .build(directory)?;
+
+        Ok(Some(tracing_appender::non_blocking(appender)))
+    }
+
+    /// Initialize Prometheus metrics and start the scrape endpoint
+    fn init_metrics(config: &MetricsConfig) -> anyhow::Result<JoinHandle<()>> {
         let bind_addr: SocketAddr = config.bind_address.parse()?;
-        
-        let builder = PrometheusBuilder::new();
-        let handle = builder.install()?;
-
-        // Start metrics server in background
-        tokio::spawn(async move {
-            let listener = std::net::TcpListener::bind(bind_addr).unwrap();
-            for stream in listener.incoming() {
-                if let Ok(_stream) = stream {
-                    // Basic HTTP metrics endpoint - in production you'd use a proper HTTP server
-                    break;
-                }
-            }
-        });
+
+        // Bind before installing so a bad address fails initialization
+        let listener = std::net::TcpListener::bind(bind_addr)?;
+        listener.set_nonblocking(true)?;
+        let listener = TcpListener::from_std(listener)?;
+
+        let handle = PrometheusBuilder::new().install_recorder()?;
+        let server = serve_metrics(listener, handle);
 
         info!("Prometheus metrics initialized on {}", bind_addr);
-        Ok(())
+        Ok(server)
+    }
+
+    /// Build the OpenTelemetry layer exporting spans over OTLP
+    #[cfg(feature = "tracing")]
+    fn tracing_layer<S>(
+        config: &TracingConfig,
+    ) -> anyhow::Result<
+        Option<tracing_opentelemetry::OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>>,
+    >
+    where
+        S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+    {
+        use opentelemetry::KeyValue;
+        use opentelemetry_otlp::WithExportConfig;
+        use opentelemetry_sdk::trace::Sampler;
+        use opentelemetry_sdk::Resource;
+
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let mut exporter = opentelemetry_otlp::new_exporter().tonic();
+        if let Some(endpoint) = &config.jaeger_endpoint {
+            exporter = exporter.with_endpoint(endpoint);
+        }
+
+        let sampler = Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            config.sampling_rate.clamp(0.0, 1.0),
+        )));
+
+        let tracer = opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(exporter)
+            .with_trace_config(
+                opentelemetry_sdk::trace::config()
+                    .with_sampler(sampler)
+                    .with_resource(Resource::new(vec![KeyValue::new("service.name", "ripel")])),
+            )
+            .install_batch(opentelemetry_sdk::runtime::Tokio)?;
+
+        Ok(Some(tracing_opentelemetry::layer().with_tracer(tracer)))
+    }
+
+    /// Trace export needs the `tracing` feature; without it no layer is added
+    #[cfg(not(feature = "tracing"))]
+    fn tracing_layer(
+        _config: &TracingConfig,
+    ) -> anyhow::Result<Option<tracing_subscriber::layer::Identity>> {
+        Ok(None)
     }
 
-    /// Initialize distributed tracing
-    fn init_tracing(_config: &TracingConfig) -> anyhow::Result<()> {
-        // For now, just log that tracing would be initialized
-        // In a full implementation, you'd set up Jaeger or similar
-        info!("Distributed tracing initialized");
+    /// Report the state of distributed tracing once logging is up
+    fn init_tracing(config: &TracingConfig) -> anyhow::Result<()> {
+        if cfg!(feature = "tracing") {
+            info!(
+                endpoint = config.jaeger_endpoint.as_deref().unwrap_or("default"),
+                sampling_rate = config.sampling_rate,
+                "Distributed tracing initialized"
+            );
+        } else {
+            warn!("Distributed tracing is enabled but ripel-shared was built without the `tracing` feature");
+        }
         Ok(())
     }
 
+    /// Whether Prometheus metrics were enabled at initialization
+    pub fn metrics_enabled(&self) -> bool {
+        self.metrics_enabled
+    }
+
+    /// Whether distributed tracing was enabled at initialization
+    pub fn tracing_enabled(&self) -> bool {
+        self.tracing_enabled
+    }
+
     /// Get the global observability system
     pub fn get() -> Option<&'static ObservabilitySystem> {
         OBSERVABILITY.get()
     }
+
+    /// Stop serving the metrics endpoint and flush the log file writer.
+    ///
+    /// Lines logged after this call are no longer written to the log file.
+    pub fn shutdown(&self) {
+        if let Some(server) = &self.metrics_server {
+            server.abort();
+        }
+        drop(self.log_guard.lock().unwrap().take());
+    }
+}
+
+/// Serve the Prometheus text format on `GET /metrics` until the returned task
+/// is aborted
+pub fn serve_metrics(listener: TcpListener, handle: PrometheusHandle) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    let handle = handle.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = respond_metrics(stream, &handle).await {
+                            debug!(peer = %peer, error = %e, "Metrics request failed");
+                        }
+                    });
+                }
+                Err(e) => {
+                    warn!(error = %e, "Failed to accept metrics connection");
+                    // Errors such as running out of file descriptors persist
+                    // for a while; don't spin on them
+                    tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                }
+            }
+        }
+    })
+}
+
+async fn respond_metrics(mut stream: TcpStream, handle: &PrometheusHandle) -> std::io::Result<()> {
+    let (method, path) = read_request_line(&mut stream).await?;
+    let (status, content_type, body) = match (method.as_str(), path.as_str()) {
+        ("GET", "/metrics") => ("200 OK", "text/plain; version=0.0.4", handle.render()),
+        ("GET", _) => ("404 Not Found", "text/plain", "Not Found\n".to_string()),
+        _ => ("405 Method Not Allowed", "text/plain", "Method Not Allowed\n".to_string()),
+    };
+
+    write_response(&mut stream, status, content_type, &body).await
+}
+
+/// Read an HTTP request head and return its method and path
+pub(crate) async fn read_request_line(stream: &mut TcpStream) -> std::io::Result<(String, String)> {
+    // Only the request line matters; headers are read up to a small limit
+    let mut request = Vec::with_capacity(1024);
+    let mut buf = [0u8; 1024];
+    let read_head = async {
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < 8192 {
+            let n = stream.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf[..n]);
+        }
+        Ok::<_, std::io::Error>(())
+    };
+
+    // A client that never finishes its request must not hold the task forever
+    tokio::time::timeout(REQUEST_READ_TIMEOUT, read_head)
+        .await
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "request read timed out"))??;
+
+    let request = String::from_utf8_lossy(&request);
+    let mut request_line = request.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line.next().unwrap_or_default().to_string();
+    Ok((method, path))
+}
+
+/// Write a complete HTTP response and close the connection
+pub(crate) async fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
 }
 
 /// Event processing metrics
@@ -145,6 +365,33 @@ impl EventMetrics {
             .increment(1);
     }
 
+    /// Record a processor in a chain failing an event
+    pub fn processor_failed(processor: &str, error_type: &str) {
+        counter!("ripel_processor_failures_total",
+                "processor" => processor.to_string(),
+                "error_type" => error_type.to_string())
+            .increment(1);
+    }
+
+    /// Record a configuration reload attempt
+    pub fn config_reload(success: bool) {
+        let status = if success { "success" } else { "error" };
+        counter!("ripel_config_reloads_total", "status" => status.to_string())
+            .increment(1);
+    }
+
+    /// Record events a lagging subscriber skipped
+    pub fn stream_lagged_events(skipped: u64) {
+        counter!("ripel_stream_lagged_events_total")
+            .increment(skipped);
+    }
+
+    /// Record a subscriber stream ended for lagging too far behind
+    pub fn stream_lag_exceeded() {
+        counter!("ripel_stream_lag_exceeded_total")
+            .increment(1);
+    }
+
     /// Record processing duration
     pub fn processing_duration(duration: Duration, event_type: &str) {
         histogram!("ripel_event_processing_duration_seconds", 
@@ -170,6 +417,12 @@ impl EventMetrics {
             .record(duration.as_secs_f64());
     }
 
+    /// Record an operation being retried
+    pub fn retry_attempt() {
+        counter!("ripel_retry_attempts_total")
+            .increment(1);
+    }
+
     /// Record Kafka operation
     pub fn kafka_operation(operation: &str, topic: &str, success: bool) {
         let status = if success { "success" } else { "error" };
@@ -186,6 +439,7 @@ pub struct PerfTimer {
     start: Instant,
     metric_name: String,
     labels: Vec<(String, String)>,
+    recorded: bool,
 }
 
 impl PerfTimer {
@@ -194,6 +448,7 @@ impl PerfTimer {
             start: Instant::now(),
             metric_name: metric_name.into(),
             labels: Vec::new(),
+            recorded: false,
         }
     }
 
@@ -202,9 +457,24 @@ impl PerfTimer {
         self
     }
 
-    pub fn finish(self) {
+    pub fn finish(mut self) {
+        self.record();
+    }
+
+    /// Record the elapsed time with the accumulated labels, at most once
+    fn record(&mut self) {
+        if self.recorded {
+            return;
+        }
+        self.recorded = true;
+
         let duration = self.start.elapsed();
-        let hist = histogram!(self.metric_name.clone());
+        let labels: Vec<Label> = self
+            .labels
+            .iter()
+            .map(|(key, value)| Label::new(key.clone(), value.clone()))
+            .collect();
+        let hist = histogram!(self.metric_name.clone(), labels);
         hist.record(duration.as_secs_f64());
     }
 }
@@ -212,9 +482,7 @@ impl PerfTimer {
 impl Drop for PerfTimer {
     fn drop(&mut self) {
         if !std::thread::panicking() {
-            let duration = self.start.elapsed();
-            let hist = histogram!(self.metric_name.clone());
-            hist.record(duration.as_secs_f64());
+            self.record();
         }
     }
 }
@@ -233,33 +501,103 @@ pub trait HealthCheck: Send + Sync {
     fn check(&self) -> HealthStatus;
 }
 
+/// Asynchronous component health check, for probes that need I/O
+#[async_trait]
+pub trait AsyncHealthCheck: Send + Sync {
+    fn name(&self) -> &str;
+    async fn check(&self) -> HealthStatus;
+}
+
 /// System health aggregator
 pub struct HealthAggregator {
-    checks: Vec<Box<dyn HealthCheck>>,
+    checks: RwLock<Vec<Arc<dyn HealthCheck>>>,
+    async_checks: RwLock<Vec<Arc<dyn AsyncHealthCheck>>>,
 }
 
 impl HealthAggregator {
     pub fn new() -> Self {
         Self {
-            checks: Vec::new(),
+            checks: RwLock::new(Vec::new()),
+            async_checks: RwLock::new(Vec::new()),
         }
     }
 
-    pub fn add_check(mut self, check: Box<dyn HealthCheck>) -> Self {
-        self.checks.push(check);
+    pub fn add_check(self, check: Box<dyn HealthCheck>) -> Self {
+        self.register(check);
         self
     }
 
+    pub fn add_async_check(self, check: Box<dyn AsyncHealthCheck>) -> Self {
+        self.register_async(check);
+        self
+    }
+
+    /// Register a check on an aggregator that is already shared
+    pub fn register(&self, check: Box<dyn HealthCheck>) {
+        self.checks.write().unwrap().push(Arc::from(check));
+    }
+
+    /// Register an async check on an aggregator that is already shared
+    pub fn register_async(&self, check: Box<dyn AsyncHealthCheck>) {
+        self.async_checks.write().unwrap().push(Arc::from(check));
+    }
+
+    /// Names of every registered check, synchronous ones first
+    pub fn check_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .checks
+            .read()
+            .unwrap()
+            .iter()
+            .map(|check| check.name().to_string())
+            .collect();
+        names.extend(
+            self.async_checks
+                .read()
+                .unwrap()
+                .iter()
+                .map(|check| check.name().to_string()),
+        );
+        names
+    }
+
+    /// Run the synchronous checks only
     pub fn check_all(&self) -> Vec<(String, HealthStatus)> {
         self.checks
+            .read()
+            .unwrap()
             .iter()
             .map(|check| (check.name().to_string(), check.check()))
             .collect()
     }
 
+    /// Run both synchronous and async checks
+    pub async fn check_all_async(&self) -> Vec<(String, HealthStatus)> {
+        let mut results = self.check_all();
+
+        let async_checks = self.async_checks.read().unwrap().clone();
+        let statuses = join_all(async_checks.iter().map(|check| check.check())).await;
+        results.extend(
+            async_checks
+                .iter()
+                .map(|check| check.name().to_string())
+                .zip(statuses),
+        );
+
+        results
+    }
+
+    /// Overall status of the synchronous checks
     pub fn overall_status(&self) -> HealthStatus {
-        let results = self.check_all();
-        
+        Self::aggregate(&self.check_all())
+    }
+
+    /// Overall status of both synchronous and async checks
+    pub async fn overall_status_async(&self) -> HealthStatus {
+        Self::aggregate(&self.check_all_async().await)
+    }
+
+    pub(crate) fn aggregate(results: &[(String, HealthStatus)]) -> HealthStatus {
         let unhealthy: Vec<_> = results
             .iter()
             .filter_map(|(name, status)| match status {
@@ -302,6 +640,41 @@ impl Default for HealthAggregator {
 mod tests {
     use super::*;
 
+    async fn scrape(addr: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes())
+            .await
+            .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        metrics::with_local_recorder(&recorder, || {
+            counter!("ripel_test_scrapes_total").increment(3);
+        });
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = serve_metrics(listener, recorder.handle());
+
+        // The server keeps accepting after the first scrape
+        for _ in 0..2 {
+            let response = scrape(addr, "/metrics").await;
+            assert!(response.starts_with("HTTP/1.1 200 OK"), "{}", response);
+            assert!(response.contains("ripel_test_scrapes_total 3"), "{}", response);
+        }
+
+        assert!(scrape(addr, "/other").await.starts_with("HTTP/1.1 404 Not Found"));
+
+        server.abort();
+    }
+
     struct TestHealthCheck {
         name: String,
         status: HealthStatus,
@@ -344,6 +717,46 @@ mod tests {
         }
     }
 
+    struct TestAsyncHealthCheck {
+        name: String,
+        status: HealthStatus,
+    }
+
+    #[async_trait]
+    impl AsyncHealthCheck for TestAsyncHealthCheck {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        async fn check(&self) -> HealthStatus {
+            self.status.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_aggregator_async_checks() {
+        let aggregator = HealthAggregator::new().add_check(Box::new(TestHealthCheck {
+            name: "sync".to_string(),
+            status: HealthStatus::Healthy,
+        }));
+
+        // Registered after construction, as a component would on start-up
+        aggregator.register_async(Box::new(TestAsyncHealthCheck {
+            name: "async".to_string(),
+            status: HealthStatus::Degraded {
+                reason: "slow".to_string(),
+            },
+        }));
+
+        assert_eq!(aggregator.check_all().len(), 1);
+        assert_eq!(aggregator.check_all_async().await.len(), 2);
+
+        match aggregator.overall_status_async().await {
+            HealthStatus::Degraded { reason } => assert_eq!(reason, "async: slow"),
+            other => panic!("Expected degraded status, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_perf_timer() {
         let timer = PerfTimer::new("test_metric")
@@ -356,4 +769,73 @@ mod tests {
         // Let it drop to test the metric recording
         drop(timer);
     }
+
+    #[test]
+    fn test_perf_timer_records_labels() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        metrics::with_local_recorder(&recorder, || {
+            PerfTimer::new("kafka_publish_batch_duration")
+                .with_label("topic", "events")
+                .with_label("batch_size", "10")
+                .finish();
+        });
+
+        let snapshot = snapshotter.snapshot().into_vec();
+        assert_eq!(snapshot.len(), 1);
+
+        let (key, _, _, value) = &snapshot[0];
+        let key = key.key();
+        assert_eq!(key.name(), "kafka_publish_batch_duration");
+
+        let labels: Vec<_> = key.labels().map(|l| (l.key(), l.value())).collect();
+        assert_eq!(labels, vec![("topic", "events"), ("batch_size", "10")]);
+
+        // finish() must not record a second sample when the timer drops
+        match value {
+            DebugValue::Histogram(samples) => assert_eq!(samples.len(), 1),
+            other => panic!("Expected histogram, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_file_logging() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join("ripel.log");
+        let config = LoggingConfig {
+            level: "info".to_string(),
+            format: "json".to_string(),
+            file_enabled: true,
+            file_path: Some(path.to_string_lossy().into_owned()),
+        };
+
+        let (writer, guard) = ObservabilitySystem::file_writer(&config).unwrap().unwrap();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_subscriber::fmt::layer().json().with_writer(writer));
+        tracing::subscriber::with_default(subscriber, || {
+            info!(event_id = "evt-1", "written to file");
+        });
+
+        // Dropping the guard flushes the background writer
+        drop(guard);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("written to file"), "{}", contents);
+        assert!(contents.contains("evt-1"), "{}", contents);
+    }
+
+    #[test]
+    fn test_file_logging_disabled() {
+        let config = LoggingConfig {
+            level: "info".to_string(),
+            format: "json".to_string(),
+            file_enabled: false,
+            file_path: Some("/nonexistent/ripel.log".to_string()),
+        };
+
+        assert!(ObservabilitySystem::file_writer(&config).unwrap().is_none());
+    }
 }
\ No newline at end of file
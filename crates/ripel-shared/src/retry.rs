@@ -1,15 +1,30 @@
 //! Retry logic and backoff strategies
 
-use std::time::Duration;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::time::sleep;
 use tracing::{debug, warn};
 use crate::config::RetryConfig;
+use crate::observability::EventMetrics;
 
 /// Retry policy trait
 pub trait RetryPolicy: Send + Sync {
     fn should_retry(&self, attempt: u32, error: &dyn std::error::Error) -> bool;
     fn delay(&self, attempt: u32) -> Duration;
     fn max_attempts(&self) -> u32;
+
+    /// Delay before retrying after `error`; defaults to [`RetryPolicy::delay`]
+    fn delay_for_error(&self, attempt: u32, _error: &(dyn std::error::Error + 'static)) -> Duration {
+        self.delay(attempt)
+    }
+}
+
+/// Errors that carry a server-provided hint for when to retry
+/// (e.g. Kafka throttle time or an HTTP `Retry-After` header)
+pub trait RetryAfter {
+    fn retry_after(&self) -> Option<Duration>;
 }
 
 /// Exponential backoff retry policy
@@ -33,8 +48,9 @@ impl ExponentialBackoff {
 }
 
 impl RetryPolicy for ExponentialBackoff {
+    /// `max_attempts` includes the first attempt
     fn should_retry(&self, attempt: u32, _error: &dyn std::error::Error) -> bool {
-        attempt < self.max_attempts
+        attempt + 1 < self.max_attempts
     }
 
     fn delay(&self, attempt: u32) -> Duration {
@@ -72,8 +88,9 @@ impl FixedInterval {
 }
 
 impl RetryPolicy for FixedInterval {
+    /// `max_attempts` includes the first attempt
     fn should_retry(&self, attempt: u32, _error: &dyn std::error::Error) -> bool {
-        attempt < self.max_attempts
+        attempt + 1 < self.max_attempts
     }
 
     fn delay(&self, _attempt: u32) -> Duration {
@@ -103,14 +120,103 @@ impl RetryPolicy for NoRetry {
     }
 }
 
+/// Retry policy adapter that waits for the delay advertised by errors of
+/// type `E`, falling back to the wrapped policy when no hint is given
+pub struct RespectRetryAfter<P, E> {
+    inner: P,
+    _error: PhantomData<fn() -> E>,
+}
+
+impl<P: RetryPolicy, E> RespectRetryAfter<P, E>
+where
+    E: RetryAfter + std::error::Error + 'static,
+{
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            _error: PhantomData,
+        }
+    }
+}
+
+impl<P: RetryPolicy, E> RetryPolicy for RespectRetryAfter<P, E>
+where
+    E: RetryAfter + std::error::Error + 'static,
+{
+    fn should_retry(&self, attempt: u32, error: &dyn std::error::Error) -> bool {
+        self.inner.should_retry(attempt, error)
+    }
+
+    fn delay(&self, attempt: u32) -> Duration {
+        self.inner.delay(attempt)
+    }
+
+    fn max_attempts(&self) -> u32 {
+        self.inner.max_attempts()
+    }
+
+    fn delay_for_error(&self, attempt: u32, error: &(dyn std::error::Error + 'static)) -> Duration {
+        error
+            .downcast_ref::<E>()
+            .and_then(RetryAfter::retry_after)
+            .unwrap_or_else(|| self.inner.delay_for_error(attempt, error))
+    }
+}
+
+/// Predicate deciding whether an error is worth retrying
+pub type RetryClassifier = Box<dyn Fn(&(dyn std::error::Error + 'static)) -> bool + Send + Sync>;
+
+/// Callback invoked before each retry with the 1-based number of the failed
+/// attempt, its error and the delay before the next attempt
+pub type RetryHook = Box<dyn Fn(u32, &(dyn std::error::Error + 'static), Duration) + Send + Sync>;
+
 /// Retry executor
 pub struct RetryExecutor<P: RetryPolicy> {
     policy: P,
+    classifier: Option<RetryClassifier>,
+    max_elapsed: Option<Duration>,
+    on_retry: Option<RetryHook>,
 }
 
 impl<P: RetryPolicy> RetryExecutor<P> {
     pub fn new(policy: P) -> Self {
-        Self { policy }
+        Self {
+            policy,
+            classifier: None,
+            max_elapsed: None,
+            on_retry: None,
+        }
+    }
+
+    /// Call `hook` every time a failed attempt is about to be retried
+    pub fn on_retry<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(u32, &(dyn std::error::Error + 'static), Duration) + Send + Sync + 'static,
+    {
+        self.on_retry = Some(Box::new(hook));
+        self
+    }
+
+    /// Stop retrying once the total time spent, including backoff sleeps,
+    /// would exceed `max_elapsed`. Unlike [`RetryExecutor::execute_with_timeout`]
+    /// this never interrupts an attempt in progress.
+    pub fn with_max_elapsed(mut self, max_elapsed: Duration) -> Self {
+        self.max_elapsed = Some(max_elapsed);
+        self
+    }
+
+    /// Only retry errors for which `predicate` returns true; any other error
+    /// is returned immediately
+    pub fn retry_if<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&(dyn std::error::Error + 'static)) -> bool + Send + Sync + 'static,
+    {
+        self.classifier = Some(Box::new(predicate));
+        self
+    }
+
+    fn is_retryable(&self, error: &(dyn std::error::Error + 'static)) -> bool {
+        self.classifier.as_ref().map_or(true, |classify| classify(error))
     }
 
     /// Execute a function with retry logic
@@ -120,6 +226,7 @@ impl<P: RetryPolicy> RetryExecutor<P> {
         E: std::error::Error + Send + 'static,
     {
         let mut attempt = 0;
+        let started = Instant::now();
 
         loop {
             match operation().await {
@@ -130,6 +237,11 @@ impl<P: RetryPolicy> RetryExecutor<P> {
                     return Ok(result);
                 }
                 Err(error) => {
+                    if !self.is_retryable(&error) {
+                        debug!("Operation failed with non-retryable error: {}", error);
+                        return Err(error);
+                    }
+
                     if !self.policy.should_retry(attempt, &error) {
                         warn!(
                             "Operation failed after {} attempts: {}",
@@ -139,14 +251,31 @@ impl<P: RetryPolicy> RetryExecutor<P> {
                         return Err(error);
                     }
 
-                    let delay = self.policy.delay(attempt);
+                    let delay = self.policy.delay_for_error(attempt, &error);
+                    if let Some(max_elapsed) = self.max_elapsed {
+                        if started.elapsed() + delay > max_elapsed {
+                            warn!(
+                                "Operation failed after {} attempts, retry deadline of {:?} reached: {}",
+                                attempt + 1,
+                                max_elapsed,
+                                error
+                            );
+                            return Err(error);
+                        }
+                    }
+
                     warn!(
                         "Operation failed (attempt {}), retrying in {:?}: {}",
                         attempt + 1,
                         delay,
                         error
                     );
-                    
+
+                    EventMetrics::retry_attempt();
+                    if let Some(hook) = &self.on_retry {
+                        hook(attempt + 1, &error, delay);
+                    }
+
                     sleep(delay).await;
                     attempt += 1;
                 }
@@ -171,6 +300,187 @@ impl<P: RetryPolicy> RetryExecutor<P> {
     }
 }
 
+/// Source of monotonic time for [`CircuitBreaker`]
+pub trait Clock: Send + Sync {
+    /// Time elapsed since an arbitrary fixed origin
+    fn elapsed(&self) -> Duration;
+}
+
+/// Clock backed by [`Instant`]
+#[derive(Debug, Clone)]
+pub struct MonotonicClock {
+    origin: Instant,
+}
+
+impl Default for MonotonicClock {
+    fn default() -> Self {
+        Self {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Clock for MonotonicClock {
+    fn elapsed(&self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+/// Circuit breaker state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls go through; consecutive failures are counted
+    Closed,
+    /// Calls fail fast until the cooldown has elapsed
+    Open,
+    /// A single probe call is in flight to decide whether to close again
+    HalfOpen,
+}
+
+impl CircuitState {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => CircuitState::Closed,
+            1 => CircuitState::Open,
+            _ => CircuitState::HalfOpen,
+        }
+    }
+}
+
+/// Circuit breaker around a [`RetryExecutor`].
+///
+/// Each call to [`CircuitBreaker::execute`] runs the full retry loop and
+/// counts as one success or failure. After `failure_threshold` consecutive
+/// failures the circuit opens and calls fail with [`RetryError::CircuitOpen`]
+/// without running the operation. Once `cooldown` has elapsed the next call
+/// is let through as a probe: success closes the circuit, failure reopens it.
+/// A probe that is dropped before it completes also reopens the circuit.
+pub struct CircuitBreaker<P: RetryPolicy> {
+    executor: RetryExecutor<P>,
+    failure_threshold: u32,
+    cooldown: Duration,
+    clock: Arc<dyn Clock>,
+    state: AtomicU8,
+    consecutive_failures: AtomicU32,
+    opened_at_ms: AtomicU64,
+}
+
+impl<P: RetryPolicy> CircuitBreaker<P> {
+    pub fn new(executor: RetryExecutor<P>, failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            executor,
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            clock: Arc::new(MonotonicClock::default()),
+            state: AtomicU8::new(CircuitState::Closed as u8),
+            consecutive_failures: AtomicU32::new(0),
+            opened_at_ms: AtomicU64::new(0),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Current state of the circuit
+    pub fn state(&self) -> CircuitState {
+        CircuitState::from_u8(self.state.load(Ordering::Acquire))
+    }
+
+    /// Execute a function with retry logic, unless the circuit is open
+    pub async fn execute<F, T, E>(&self, operation: F) -> Result<T, RetryError<E>>
+    where
+        F: FnMut() -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<T, E>> + Send>>,
+        E: std::error::Error + Send + 'static,
+    {
+        let probe = self.acquire()?.then(|| ProbeGuard { breaker: self });
+
+        let result = self.executor.execute(operation).await;
+        // The probe finished, so its outcome decides the state from here on
+        std::mem::forget(probe);
+
+        match result {
+            Ok(result) => {
+                self.on_success();
+                Ok(result)
+            }
+            Err(error) => {
+                self.on_failure();
+                Err(RetryError::Operation(error))
+            }
+        }
+    }
+
+    fn now_ms(&self) -> u64 {
+        self.clock.elapsed().as_millis() as u64
+    }
+
+    /// Check whether a call may run; `Ok(true)` means it is the half-open probe
+    fn acquire<E>(&self) -> Result<bool, RetryError<E>> {
+        match self.state() {
+            CircuitState::Closed => Ok(false),
+            CircuitState::HalfOpen => Err(RetryError::CircuitOpen),
+            CircuitState::Open => {
+                let opened_at = self.opened_at_ms.load(Ordering::Acquire);
+                if self.now_ms().saturating_sub(opened_at) < self.cooldown.as_millis() as u64 {
+                    return Err(RetryError::CircuitOpen);
+                }
+
+                // Only the caller that wins the transition gets to probe
+                self.state
+                    .compare_exchange(
+                        CircuitState::Open as u8,
+                        CircuitState::HalfOpen as u8,
+                        Ordering::AcqRel,
+                        Ordering::Acquire,
+                    )
+                    .map(|_| {
+                        debug!("Circuit breaker half-open, probing");
+                        true
+                    })
+                    .map_err(|_| RetryError::CircuitOpen)
+            }
+        }
+    }
+
+    fn on_success(&self) {
+        self.consecutive_failures.store(0, Ordering::Release);
+        if self.state.swap(CircuitState::Closed as u8, Ordering::AcqRel) != CircuitState::Closed as u8 {
+            debug!("Circuit breaker closed");
+        }
+    }
+
+    fn on_failure(&self) {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::AcqRel) + 1;
+        if self.state() == CircuitState::HalfOpen || failures >= self.failure_threshold {
+            self.opened_at_ms.store(self.now_ms(), Ordering::Release);
+            if self.state.swap(CircuitState::Open as u8, Ordering::AcqRel) != CircuitState::Open as u8 {
+                warn!(
+                    consecutive_failures = failures,
+                    cooldown = ?self.cooldown,
+                    "Circuit breaker opened"
+                );
+            }
+        }
+    }
+}
+
+/// Reopens a half-open circuit when its probe is dropped mid-flight, so the
+/// breaker does not stay half-open and reject every later call
+struct ProbeGuard<'a, P: RetryPolicy> {
+    breaker: &'a CircuitBreaker<P>,
+}
+
+impl<P: RetryPolicy> Drop for ProbeGuard<'_, P> {
+    fn drop(&mut self) {
+        let breaker = self.breaker;
+        breaker.opened_at_ms.store(breaker.now_ms(), Ordering::Release);
+        breaker.state.store(CircuitState::Open as u8, Ordering::Release);
+        debug!("Circuit breaker probe abandoned, reopening");
+    }
+}
+
 /// Retry-specific errors
 #[derive(Debug, thiserror::Error)]
 pub enum RetryError<E> {
@@ -179,6 +489,9 @@ pub enum RetryError<E> {
     
     #[error("Operation timed out")]
     Timeout,
+
+    #[error("Circuit breaker is open")]
+    CircuitOpen,
 }
 
 /// Convenience function to create an exponential backoff executor
@@ -243,7 +556,7 @@ mod tests {
         let attempt_count = Arc::new(AtomicU32::new(0));
         
         let attempt_count_clone = attempt_count.clone();
-        let result = executor
+        let result: Result<(), TestError> = executor
             .execute(move || {
                 let attempt_count = attempt_count_clone.clone();
                 Box::pin(async move {
@@ -257,13 +570,50 @@ mod tests {
         assert_eq!(attempt_count.load(Ordering::Relaxed), 2);
     }
 
+    async fn count_attempts<P: RetryPolicy>(policy: P) -> u32 {
+        let executor = RetryExecutor::new(policy);
+        let attempt_count = Arc::new(AtomicU32::new(0));
+
+        let attempt_count_clone = attempt_count.clone();
+        let result: Result<(), TestError> = executor
+            .execute(move || {
+                let attempt_count = attempt_count_clone.clone();
+                Box::pin(async move {
+                    attempt_count.fetch_add(1, Ordering::Relaxed);
+                    Err(TestError)
+                })
+            })
+            .await;
+
+        assert!(result.is_err());
+        attempt_count.load(Ordering::Relaxed)
+    }
+
+    #[tokio::test]
+    async fn test_max_attempts_counts_the_first_attempt() {
+        let config = RetryConfig {
+            initial_delay_ms: 1,
+            max_delay_ms: 1,
+            multiplier: 1.0,
+            jitter_ms: 0,
+        };
+
+        for max_attempts in 1..=4 {
+            let fixed = FixedInterval::new(Duration::from_millis(1), max_attempts);
+            assert_eq!(count_attempts(fixed).await, max_attempts);
+
+            let exponential = ExponentialBackoff::new(config.clone(), max_attempts);
+            assert_eq!(count_attempts(exponential).await, max_attempts);
+        }
+    }
+
     #[tokio::test]
     async fn test_no_retry() {
         let executor = RetryExecutor::new(NoRetry);
         let attempt_count = Arc::new(AtomicU32::new(0));
         
         let attempt_count_clone = attempt_count.clone();
-        let result = executor
+        let result: Result<(), TestError> = executor
             .execute(move || {
                 let attempt_count = attempt_count_clone.clone();
                 Box::pin(async move {
@@ -280,7 +630,7 @@ mod tests {
     #[tokio::test]
     async fn test_timeout() {
         let executor = RetryExecutor::new(NoRetry);
-        let result = executor
+        let result: Result<_, RetryError<TestError>> = executor
             .execute_with_timeout(
                 move || {
                     Box::pin(async move {
@@ -294,4 +644,239 @@ mod tests {
 
         assert!(matches!(result, Err(RetryError::Timeout)));
     }
-}
\ No newline at end of file
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("Throttled")]
+    struct ThrottledError {
+        retry_after: Option<Duration>,
+    }
+
+    impl RetryAfter for ThrottledError {
+        fn retry_after(&self) -> Option<Duration> {
+            self.retry_after
+        }
+    }
+
+    #[tokio::test]
+    async fn test_respect_retry_after() {
+        let policy: RespectRetryAfter<_, ThrottledError> =
+            RespectRetryAfter::new(FixedInterval::new(Duration::from_millis(1), 2));
+        let executor = RetryExecutor::new(policy);
+        let attempt_count = Arc::new(AtomicU32::new(0));
+
+        let attempt_count_clone = attempt_count.clone();
+        let started = tokio::time::Instant::now();
+        let result = executor
+            .execute(move || {
+                let attempt_count = attempt_count_clone.clone();
+                Box::pin(async move {
+                    if attempt_count.fetch_add(1, Ordering::Relaxed) == 0 {
+                        Err(ThrottledError {
+                            retry_after: Some(Duration::from_millis(50)),
+                        })
+                    } else {
+                        Ok("success")
+                    }
+                })
+            })
+            .await;
+
+        assert!(result.is_ok());
+        assert_eq!(attempt_count.load(Ordering::Relaxed), 2);
+        assert!(started.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_retry_after_falls_back_without_hint() {
+        let policy: RespectRetryAfter<_, ThrottledError> =
+            RespectRetryAfter::new(FixedInterval::new(Duration::from_millis(7), 3));
+
+        let hinted = ThrottledError { retry_after: Some(Duration::from_secs(2)) };
+        let unhinted = ThrottledError { retry_after: None };
+
+        assert_eq!(policy.delay_for_error(0, &hinted), Duration::from_secs(2));
+        assert_eq!(policy.delay_for_error(0, &unhinted), Duration::from_millis(7));
+        assert_eq!(policy.delay_for_error(0, &TestError), Duration::from_millis(7));
+    }
+
+    #[derive(Default)]
+    struct FakeClock {
+        now_ms: AtomicU64,
+    }
+
+    impl FakeClock {
+        fn advance(&self, by: Duration) {
+            self.now_ms.fetch_add(by.as_millis() as u64, Ordering::Relaxed);
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn elapsed(&self) -> Duration {
+            Duration::from_millis(self.now_ms.load(Ordering::Relaxed))
+        }
+    }
+
+    async fn run(
+        breaker: &CircuitBreaker<NoRetry>,
+        calls: &Arc<AtomicU32>,
+        succeed: bool,
+    ) -> Result<(), RetryError<TestError>> {
+        let calls = calls.clone();
+        breaker
+            .execute(move || {
+                let calls = calls.clone();
+                Box::pin(async move {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    if succeed {
+                        Ok(())
+                    } else {
+                        Err(TestError)
+                    }
+                })
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_transitions() {
+        let clock = Arc::new(FakeClock::default());
+        let breaker = CircuitBreaker::new(RetryExecutor::new(NoRetry), 2, Duration::from_secs(30))
+            .with_clock(clock.clone());
+        let calls = Arc::new(AtomicU32::new(0));
+
+        // Closed: failures below the threshold still run the operation
+        assert!(matches!(run(&breaker, &calls, false).await, Err(RetryError::Operation(_))));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(matches!(run(&breaker, &calls, false).await, Err(RetryError::Operation(_))));
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+
+        // Open: fail fast without calling the operation
+        assert!(matches!(run(&breaker, &calls, true).await, Err(RetryError::CircuitOpen)));
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+
+        // Half-open: a failed probe reopens for another cooldown
+        clock.advance(Duration::from_secs(30));
+        assert!(matches!(run(&breaker, &calls, false).await, Err(RetryError::Operation(_))));
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(matches!(run(&breaker, &calls, true).await, Err(RetryError::CircuitOpen)));
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+
+        // Half-open: a successful probe closes the circuit
+        clock.advance(Duration::from_secs(30));
+        assert!(run(&breaker, &calls, true).await.is_ok());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(run(&breaker, &calls, true).await.is_ok());
+        assert_eq!(calls.load(Ordering::Relaxed), 5);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_reopens_after_dropped_probe() {
+        use futures::FutureExt;
+
+        let clock = Arc::new(FakeClock::default());
+        let breaker = CircuitBreaker::new(RetryExecutor::new(NoRetry), 1, Duration::from_secs(30))
+            .with_clock(clock.clone());
+        let calls = Arc::new(AtomicU32::new(0));
+
+        assert!(run(&breaker, &calls, false).await.is_err());
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // The probe never completes and is dropped while half-open
+        clock.advance(Duration::from_secs(30));
+        let probe = breaker.execute(|| Box::pin(std::future::pending::<Result<(), TestError>>()));
+        assert!(probe.now_or_never().is_none());
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // The cooldown restarts from the abandoned probe
+        assert!(matches!(run(&breaker, &calls, true).await, Err(RetryError::CircuitOpen)));
+        clock.advance(Duration::from_secs(30));
+        assert!(run(&breaker, &calls, true).await.is_ok());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_retry_if_skips_non_retryable_errors() {
+        let executor = RetryExecutor::new(FixedInterval::new(Duration::from_millis(1), 5))
+            .retry_if(|error| error.is::<ThrottledError>());
+        let attempt_count = Arc::new(AtomicU32::new(0));
+
+        let attempt_count_clone = attempt_count.clone();
+        let result: Result<(), TestError> = executor
+            .execute(move || {
+                let attempt_count = attempt_count_clone.clone();
+                Box::pin(async move {
+                    attempt_count.fetch_add(1, Ordering::Relaxed);
+                    Err(TestError)
+                })
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempt_count.load(Ordering::Relaxed), 1);
+
+        let attempt_count_clone = attempt_count.clone();
+        let result: Result<(), ThrottledError> = executor
+            .execute(move || {
+                let attempt_count = attempt_count_clone.clone();
+                Box::pin(async move {
+                    attempt_count.fetch_add(1, Ordering::Relaxed);
+                    Err(ThrottledError { retry_after: None })
+                })
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempt_count.load(Ordering::Relaxed), 6);
+    }
+
+    #[tokio::test]
+    async fn test_max_elapsed_stops_before_max_attempts() {
+        let executor = RetryExecutor::new(FixedInterval::new(Duration::from_millis(20), 100))
+            .with_max_elapsed(Duration::from_millis(50));
+        let attempt_count = Arc::new(AtomicU32::new(0));
+
+        let attempt_count_clone = attempt_count.clone();
+        let started = Instant::now();
+        let result: Result<(), TestError> = executor
+            .execute(move || {
+                let attempt_count = attempt_count_clone.clone();
+                Box::pin(async move {
+                    attempt_count.fetch_add(1, Ordering::Relaxed);
+                    Err(TestError)
+                })
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_millis(100));
+        let attempts = attempt_count.load(Ordering::Relaxed);
+        assert!((2..=3).contains(&attempts), "unexpected attempt count {}", attempts);
+    }
+
+    #[tokio::test]
+    async fn test_on_retry_hook() {
+        let retries = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let retries_clone = retries.clone();
+        let executor = RetryExecutor::new(FixedInterval::new(Duration::from_millis(5), 3))
+            .on_retry(move |attempt, error, delay| {
+                retries_clone.lock().unwrap().push((attempt, error.to_string(), delay));
+            });
+
+        let result: Result<(), RetryError<TestError>> = executor
+            .execute_with_timeout(
+                || Box::pin(async { Err(TestError) }),
+                Duration::from_secs(1),
+            )
+            .await;
+
+        assert!(matches!(result, Err(RetryError::Operation(_))));
+        assert_eq!(
+            *retries.lock().unwrap(),
+            vec![
+                (1, "Test error".to_string(), Duration::from_millis(5)),
+                (2, "Test error".to_string(), Duration::from_millis(5)),
+            ]
+        );
+    }
+}
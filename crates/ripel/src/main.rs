@@ -25,6 +25,7 @@ async fn main() -> anyhow::Result<()> {
     // Create processing pipeline
     let pipeline = EventPipeline::new(Arc::new(chain), 100, 2);
     let sender = pipeline.sender();
+    let shutdown = pipeline.shutdown_handle();
     
     // Start pipeline in background
     let pipeline_handle = tokio::spawn(pipeline.start());
@@ -63,10 +64,15 @@ async fn main() -> anyhow::Result<()> {
     drop(sender);
     event_stream.stop().await?;
     
-    // Wait a bit for pipeline to finish
-    sleep(Duration::from_millis(500)).await;
-    pipeline_handle.abort();
+    // Drain queued events and stop the pipeline
+    shutdown.shutdown();
+    let stats = pipeline_handle.await??;
     
-    info!("RIPeL example completed");
+    info!(processed = stats.processed, failed = stats.failed, "RIPeL example completed");
+
+    // Flush buffered log lines before exit
+    if let Some(observability) = ObservabilitySystem::get() {
+        observability.shutdown();
+    }
     Ok(())
 }
\ No newline at end of file
@@ -68,5 +68,10 @@ async fn main() -> anyhow::Result<()> {
     pipeline_handle.abort();
     
     info!("RIPeL example completed");
+
+    // Flush buffered log lines before exit
+    if let Some(observability) = ObservabilitySystem::get() {
+        observability.shutdown();
+    }
     Ok(())
 }
\ No newline at end of file